@@ -10,8 +10,18 @@ use serde::{Deserialize, Serialize};
 use tera::{Context, Tera};
 
 fn main() -> anyhow::Result<()> {
+    for path in [
+        "./build.rs",
+        "./languages.yaml",
+        "./templates",
+        "./tests/test_config.yaml",
+    ] {
+        println!("cargo:rerun-if-changed={}", path);
+    }
     let template = {
         let mut t = Tera::default();
+        t.add_template_file("./templates/macros.tera.rs", Some("macros"))
+            .expect("Error loading macro template");
         t.add_template_file("./templates/tests.tera.rs", Some("tests"))
             .expect("Error loading test template");
         t.add_template_file("./templates/language_type.tera.rs", Some("lang_type"))
@@ -60,7 +70,7 @@ fn generate_tests(template: &Tera) -> anyhow::Result<()> {
                 name: d.name,
                 file: d.file,
                 predict: d.stats.ser(0),
-                detect: d.file_detect
+                detect: d.file_detect,
             })
         }
         v
@@ -92,6 +102,10 @@ struct TestData {
     code: usize,
     blank: usize,
     all: usize,
+    #[serde(default)]
+    complexity: usize,
+    #[serde(default)]
+    preprocessor: usize,
     sub_language: BTreeMap<String, TestData>,
     comment: TestCommentData,
 }
@@ -103,6 +117,8 @@ impl TestData {
     code: {},
     blank: {},
     all: {},
+    complexity: {},
+    preprocessor: {},
     comment: CommentResult {{
         doc: {},
         normal: {},
@@ -118,6 +134,8 @@ impl TestData {
             self.code,
             self.blank,
             self.all,
+            self.complexity,
+            self.preprocessor,
             self.comment.doc,
             self.comment.normal,
             self.comment.doc_quote,
@@ -165,7 +183,7 @@ fn generate_language(template: &Tera) -> anyhow::Result<()> {
 
     let context = {
         let mut v = Vec::new();
-        for (ident, def) in lang {
+        for (ident, mut def) in lang {
             v.push(Lang {
                 ident,
                 aliases: {
@@ -174,10 +192,15 @@ fn generate_language(template: &Tera) -> anyhow::Result<()> {
                     iv
                 },
                 name: def.name,
-                syntax: generate_syntax(template, def.syntax)?,
+                syntax: {
+                    def.syntax.quote.expand();
+                    def.syntax.check_regex()?;
+                    generate_syntax(template, def.syntax)?
+                },
                 file: {
                     def.file.extension.check_regex()?;
                     def.file.file_name.check_regex()?;
+                    def.file.translate_path()?;
                     generate_file_definition(template, def.file)?
                 },
             })
@@ -226,6 +249,72 @@ struct LanguageDefinition {
 struct LanguageFile {
     extension: FileSetting,
     file_name: FileSetting,
+    /// Globs over the relative path, rendered as regular expressions.
+    #[serde(default = "empty_vec")]
+    path: Vec<String>,
+}
+
+impl LanguageFile {
+    fn translate_path(&mut self) -> Result<(), regex::Error> {
+        for glob in self.path.iter_mut() {
+            *glob = glob_regex(glob);
+            Regex::new(glob)?;
+        }
+        Ok(())
+    }
+}
+
+/// Translate a glob over a relative path, with `/` separators, into a regular
+/// expression matching the whole path or one of its trailing components.
+///
+/// `*` and `?` stay within a component, `**/` spans components, `[!a-z]`
+/// negates a class and `{a,b}` matches either alternative.
+fn glob_regex(glob: &str) -> String {
+    let mut regex = String::from("(?:^|/)");
+    let mut braces = 0;
+    let mut chars = glob.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.next_if_eq(&'/').is_some() {
+                    regex.push_str("(?:.*/)?");
+                } else {
+                    regex.push_str(".*");
+                }
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            '[' => {
+                regex.push('[');
+                if chars.next_if_eq(&'!').is_some() {
+                    regex.push('^');
+                }
+                for c in chars.by_ref() {
+                    if c == ']' {
+                        break;
+                    }
+                    if c == '\\' || c == '[' {
+                        regex.push('\\');
+                    }
+                    regex.push(c);
+                }
+                regex.push(']');
+            }
+            '{' => {
+                braces += 1;
+                regex.push_str("(?:");
+            }
+            '}' if braces > 0 => {
+                braces -= 1;
+                regex.push(')');
+            }
+            ',' if braces > 0 => regex.push('|'),
+            c => regex.push_str(&regex::escape(c.encode_utf8(&mut [0; 4]))),
+        }
+    }
+    regex.push('$');
+    regex
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -254,13 +343,48 @@ impl FileSetting {
 struct LanguageSyntax {
     #[serde(default = "empty_vec")]
     block: Vec<(String, String)>,
-    line_prefix: Option<String>,
+    #[serde(default = "empty_vec")]
+    template: Vec<(String, String)>,
+    #[serde(default, deserialize_with = "one_or_many")]
+    line_prefix: Vec<String>,
+    #[serde(default)]
+    missing_prefix: MissingPrefix,
+    #[serde(default)]
+    literate: bool,
+    #[serde(default = "empty_vec")]
+    literate_code: Vec<CapturedPair>,
+    columns: Option<(usize, usize)>,
     #[serde(default = "true_func")]
     ignore_prefix_space: bool,
     comment: LanguageComment,
     quote: LanguageQuote,
     #[serde(default = "empty_vec")]
     sub_language: Vec<(String, String, String)>,
+    #[serde(default = "empty_vec")]
+    captured_sub_language: Vec<CapturedSubLanguage>,
+    #[serde(default = "empty_vec")]
+    doc_code: Vec<CapturedSubLanguage>,
+    #[serde(default = "empty_vec")]
+    injection: Vec<Injection>,
+    #[serde(default = "empty_vec")]
+    complexity: Vec<String>,
+    #[serde(default = "empty_vec")]
+    preprocessor: Vec<String>,
+}
+
+/// A single string, or a list of them.
+fn one_or_many<'de, D: serde::Deserializer<'de>>(de: D) -> Result<Vec<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+    Ok(match Option::<OneOrMany>::deserialize(de)? {
+        None => Vec::new(),
+        Some(OneOrMany::One(one)) => vec![one],
+        Some(OneOrMany::Many(many)) => many,
+    })
 }
 
 fn true_func() -> bool {
@@ -274,21 +398,245 @@ fn empty_vec<T>() -> Vec<T> {
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 struct LanguageComment {
     #[serde(default = "empty_vec")]
-    multi: Vec<(String, String)>,
+    multi: Vec<CommentPair>,
+    #[serde(default)]
+    word_start: bool,
+    #[serde(default = "empty_vec")]
+    end: Vec<String>,
     #[serde(default = "empty_vec")]
     single: Vec<String>,
     #[serde(default = "empty_vec")]
     doc: Vec<String>,
     #[serde(default = "empty_vec")]
-    doc_multi: Vec<(String, String)>,
+    doc_multi: Vec<CommentPair>,
+    #[serde(default = "empty_vec")]
+    captured: Vec<CapturedPair>,
+    #[serde(default = "empty_vec")]
+    doc_captured: Vec<CapturedPair>,
+    #[serde(default = "empty_vec")]
+    datum: Vec<String>,
+    #[serde(default = "empty_vec")]
+    column: Vec<(usize, String)>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(from = "CommentPairDef")]
+struct CommentPair {
+    left: String,
+    right: String,
+    nested: bool,
+}
+
+/// A comment pair is written either as `[left, right]` or as a mapping with
+/// the `nested` flag.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum CommentPairDef {
+    Plain(String, String),
+    Full {
+        left: String,
+        right: String,
+        #[serde(default)]
+        nested: bool,
+    },
+}
+
+impl From<CommentPairDef> for CommentPair {
+    fn from(def: CommentPairDef) -> Self {
+        match def {
+            CommentPairDef::Plain(left, right) => Self {
+                left,
+                right,
+                nested: false,
+            },
+            CommentPairDef::Full {
+                left,
+                right,
+                nested,
+            } => Self {
+                left,
+                right,
+                nested,
+            },
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 struct LanguageQuote {
     #[serde(default = "empty_vec")]
-    normal: Vec<(String, String)>,
+    normal: Vec<QuotePair>,
     #[serde(default = "empty_vec")]
-    literal: Vec<(String, String)>,
+    literal: Vec<QuotePair>,
     #[serde(default = "empty_vec")]
-    doc: Vec<(String, String)>,
+    doc: Vec<QuotePair>,
+    #[serde(default = "empty_vec")]
+    captured: Vec<CapturedPair>,
+    #[serde(default = "empty_vec")]
+    raw: Vec<RawQuote>,
+    #[serde(default = "empty_vec", rename = "char")]
+    char_quote: Vec<String>,
+}
+
+/// A raw string whose quote may be padded by any number of a repeated
+/// character, as Rust's `r#"..."#`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct RawQuote {
+    prefix: String,
+    repeat: String,
+    quote: String,
+}
+
+impl LanguageQuote {
+    /// Turn raw strings and character literals into captured pairs, which
+    /// come before the ones written as such.
+    fn expand(&mut self) {
+        let raw = self.raw.iter().map(|raw| CapturedPair {
+            left: format!(
+                "{}(?P<raw>(?:{})*){}",
+                regex::escape(&raw.prefix),
+                regex::escape(&raw.repeat),
+                regex::escape(&raw.quote)
+            ),
+            right: format!("{}${{raw}}", raw.quote),
+            ..Default::default()
+        });
+        // A character literal holds one character or one escape, so that a
+        // quote starting a lifetime or a label is left to the code.
+        let chars = self.char_quote.iter().map(|quote| {
+            let quote = regex::escape(quote);
+            CapturedPair {
+                left: format!(r"{0}(?:\\[^{0}\n]+|\\{0}|[^{0}\\\n]){0}", quote),
+                ..Default::default()
+            }
+        });
+        self.captured = raw.chain(chars).chain(self.captured.drain(..)).collect();
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(from = "QuotePairDef")]
+struct QuotePair {
+    left: String,
+    right: String,
+    escape: Option<Escape>,
+    multiline: bool,
+}
+
+/// A quote pair is written either as `[left, right]` or as a mapping with its
+/// escape rule and whether it may span lines.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum QuotePairDef {
+    Plain(String, String),
+    Full {
+        left: String,
+        right: String,
+        escape: Option<Escape>,
+        #[serde(default = "true_func")]
+        multiline: bool,
+    },
+}
+
+impl From<QuotePairDef> for QuotePair {
+    fn from(def: QuotePairDef) -> Self {
+        match def {
+            QuotePairDef::Plain(left, right) => Self {
+                left,
+                right,
+                escape: None,
+                multiline: true,
+            },
+            QuotePairDef::Full {
+                left,
+                right,
+                escape,
+                multiline,
+            } => Self {
+                left,
+                right,
+                escape,
+                multiline,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all(deserialize = "lowercase"))]
+enum MissingPrefix {
+    Code,
+    #[default]
+    Prose,
+    Blank,
+    Error,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all(deserialize = "lowercase"))]
+enum Escape {
+    Backslash,
+    Doubled,
+    None,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct CapturedPair {
+    left: String,
+    #[serde(default)]
+    right: String,
+    #[serde(default)]
+    next_line: bool,
+    #[serde(default)]
+    open_end: bool,
+    #[serde(default)]
+    line_start: bool,
+    #[serde(default)]
+    after_blank: bool,
+    #[serde(default)]
+    unindented: bool,
+    #[serde(default)]
+    indented: bool,
+    #[serde(default)]
+    continued: bool,
+    #[serde(default)]
+    flexible: bool,
+    escape: Option<Escape>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct CapturedSubLanguage {
+    #[serde(flatten)]
+    pair: CapturedPair,
+    language: String,
+    language_group: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct Injection {
+    comment: Option<String>,
+    before: Option<String>,
+    language: String,
+    language_group: Option<String>,
+}
+
+impl LanguageSyntax {
+    fn check_regex(&self) -> Result<(), regex::Error> {
+        let pairs = self.quote.captured.iter().chain(&self.comment.captured);
+        for pair in pairs
+            .chain(&self.comment.doc_captured)
+            .chain(&self.literate_code)
+        {
+            Regex::new(&pair.left)?;
+        }
+        for sub in self.captured_sub_language.iter().chain(&self.doc_code) {
+            Regex::new(&sub.pair.left)?;
+        }
+        for injection in self.injection.iter() {
+            for pattern in injection.comment.iter().chain(&injection.before) {
+                Regex::new(pattern)?;
+            }
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,169 @@
+use crate::{
+    error::CoreError,
+    language::{LanguageType, SyntaxType},
+    parser::{consumed, match_captured, CoreParser, LinePosition, Segment},
+};
+
+/// The text of a documentation comment or doc string, without delimiters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocBlock {
+    pub syntax_type: SyntaxType,
+    /// First line (1-based) of the block.
+    pub start_line: usize,
+    /// Last line (1-based) of the block.
+    pub end_line: usize,
+    pub text: String,
+    pub sub_language: Option<LanguageType>,
+}
+
+struct PendingDoc<'a> {
+    block: DocBlock,
+    /// Prefix of single-line doc comments, which are grouped by prefix.
+    prefix: &'a str,
+    right: String,
+    lines: Vec<&'a str>,
+}
+
+impl CoreParser {
+    /// Extract the documentation of the content.
+    ///
+    /// Consecutive single-line doc comments sharing a prefix (e.g. `///`) form
+    /// one block. Delimiters, the leading `*` decoration of multi-line doc
+    /// comments, and common indentation are stripped.
+    pub fn doc_blocks(&self) -> Result<Vec<DocBlock>, CoreError> {
+        let mut blocks = Vec::new();
+        let mut pending: Option<PendingDoc> = None;
+        self.parse_with_segments(&mut |seg: Segment| {
+            if !seg.is_doc() {
+                if seg.syntax_type != SyntaxType::Blank && !seg.text.trim().is_empty() {
+                    blocks.extend(pending.take().map(finish_doc));
+                }
+                return;
+            }
+            let syntax = seg
+                .sub_language
+                .map(|lang| lang.get_language_syntax())
+                .unwrap_or_else(|| self.syntax().clone());
+            if let Some(doc) = pending.as_mut() {
+                let same_block = doc.block.syntax_type == seg.syntax_type
+                    && doc.block.sub_language == seg.sub_language
+                    && match seg.syntax_type {
+                        SyntaxType::DocComment => {
+                            doc.block.end_line + 1 == seg.line && seg.text.starts_with(doc.prefix)
+                        }
+                        _ => seg.continued,
+                    };
+                if same_block {
+                    doc.block.end_line = seg.line;
+                    doc.lines.push(seg.text);
+                    return;
+                }
+            }
+            blocks.extend(pending.take().map(finish_doc));
+            let (prefix, right) = match seg.syntax_type {
+                SyntaxType::DocComment => (
+                    syntax
+                        .doc_comment
+                        .iter()
+                        .filter(|p| seg.text.starts_with(*p))
+                        .max_by_key(|p| p.len())
+                        .copied()
+                        .unwrap_or_default(),
+                    String::new(),
+                ),
+                SyntaxType::DocMultiComment => syntax
+                    .doc_comment_pairs
+                    .iter()
+                    .filter(|p| seg.text.starts_with(p.left))
+                    .max_by_key(|p| p.left.len())
+                    .map(|p| (p.left, p.right.to_string()))
+                    .or_else(|| {
+                        let (rest, _, closer) = match_captured(
+                            syntax.captured_doc_comment_pairs,
+                            |p| p,
+                            seg.text,
+                            LinePosition::AfterBlank,
+                        )?;
+                        Some((consumed(seg.text, rest), closer))
+                    })
+                    .unwrap_or_default(),
+                _ => syntax
+                    .doc_quote_pairs
+                    .iter()
+                    .filter(|p| seg.text.starts_with(p.left))
+                    .max_by_key(|p| p.left.len())
+                    .map_or(("", String::new()), |p| (p.left, p.right.to_string())),
+            };
+            pending = Some(PendingDoc {
+                block: DocBlock {
+                    syntax_type: seg.syntax_type,
+                    start_line: seg.line,
+                    end_line: seg.line,
+                    text: String::new(),
+                    sub_language: seg.sub_language,
+                },
+                prefix,
+                right,
+                lines: vec![seg.text],
+            });
+        })?;
+        blocks.extend(pending.take().map(finish_doc));
+        Ok(blocks)
+    }
+}
+
+fn finish_doc(doc: PendingDoc) -> DocBlock {
+    let PendingDoc {
+        mut block,
+        prefix,
+        right,
+        mut lines,
+    } = doc;
+    if block.syntax_type == SyntaxType::DocComment {
+        block.text = lines
+            .iter()
+            .map(|line| {
+                let line = &line[prefix.len()..];
+                line.strip_prefix(' ').unwrap_or(line).trim_end()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        return block;
+    }
+
+    lines[0] = &lines[0][prefix.len()..];
+    if let Some(last) = lines.last_mut() {
+        let trimmed = last.trim_end();
+        *last = trimmed.strip_suffix(right.as_str()).unwrap_or(trimmed);
+    }
+    let mut body: Vec<&str> = lines[1..]
+        .iter()
+        .map(|line| {
+            let trimmed = line.trim_start();
+            match trimmed.strip_prefix('*') {
+                Some(rest) if block.syntax_type == SyntaxType::DocMultiComment => {
+                    rest.strip_prefix(' ').unwrap_or(rest)
+                }
+                _ => line,
+            }
+        })
+        .collect();
+    let indent = body
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    for line in body.iter_mut() {
+        *line = line.get(indent..).unwrap_or("").trim_end();
+    }
+    body.insert(0, lines[0].trim());
+    while body.first().is_some_and(|line| line.is_empty()) {
+        body.remove(0);
+    }
+    while body.last().is_some_and(|line| line.is_empty()) {
+        body.pop();
+    }
+    block.text = body.join("\n");
+    block
+}
@@ -0,0 +1,123 @@
+use itertools::Itertools;
+use serde::Serialize;
+
+use crate::{error::CoreError, language::SyntaxType, parser::CoreParser};
+
+const SPDX_TAG: &str = "SPDX-License-Identifier:";
+
+/// Known license header texts, as SPDX identifiers and the phrases that must
+/// all appear in the header.
+pub const KNOWN_LICENSES: &[(&str, &[&str])] = &[
+    ("MIT", &["permission is hereby granted free of charge"]),
+    (
+        "Apache-2.0",
+        &["licensed under the apache license version 2.0"],
+    ),
+    ("MPL-2.0", &["mozilla public license v 2.0"]),
+    (
+        "AGPL-3.0",
+        &["gnu affero general public license", "version 3"],
+    ),
+    (
+        "LGPL-3.0",
+        &["gnu lesser general public license", "version 3"],
+    ),
+    (
+        "LGPL-2.1",
+        &["gnu lesser general public license", "version 2.1"],
+    ),
+    ("GPL-3.0", &["gnu general public license", "version 3"]),
+    ("GPL-2.0", &["gnu general public license", "version 2"]),
+    (
+        "BSD-3-Clause",
+        &[
+            "redistribution and use in source and binary forms",
+            "neither the name of",
+        ],
+    ),
+    (
+        "BSD-2-Clause",
+        &["redistribution and use in source and binary forms"],
+    ),
+    (
+        "Unlicense",
+        &["this is free and unencumbered software released into the public domain"],
+    ),
+];
+
+/// License information found in the leading comment block of a file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct LicenseInfo {
+    /// First and last line (1-based) of the leading comment block.
+    pub header: Option<(usize, usize)>,
+    /// Expressions of `SPDX-License-Identifier` tags.
+    pub spdx: Vec<String>,
+    /// SPDX identifiers of the license texts recognized in the header.
+    pub detected: Vec<&'static str>,
+}
+
+impl LicenseInfo {
+    /// Whether the file lacks a license header, i.e. neither a SPDX tag nor
+    /// a known license text was found.
+    pub fn is_missing(&self) -> bool {
+        self.spdx.is_empty() && self.detected.is_empty()
+    }
+}
+
+impl CoreParser {
+    /// Inspect the comments preceding the first line of code.
+    pub fn license(&self) -> Result<LicenseInfo, CoreError> {
+        let closers: Vec<&str> = self
+            .syntax()
+            .comment_pairs
+            .iter()
+            .chain(self.syntax().doc_comment_pairs)
+            .map(|p| p.right)
+            .collect();
+        let mut info = LicenseInfo::default();
+        let mut text = String::new();
+        let mut in_header = true;
+        self.parse_with_segments(&mut |seg| {
+            if !in_header || seg.sub_language.is_some() {
+                return;
+            }
+            if seg.is_comment() {
+                info.header = Some((info.header.map_or(seg.line, |h| h.0), seg.line));
+                if let Some(idx) = seg.text.find(SPDX_TAG) {
+                    let mut expr = seg.text[idx + SPDX_TAG.len()..].trim();
+                    if let Some(closer) = closers.iter().find(|c| expr.ends_with(*c)) {
+                        expr = expr[..expr.len() - closer.len()].trim_end();
+                    }
+                    if !expr.is_empty() {
+                        info.spdx.push(expr.to_string());
+                    }
+                }
+                text.push(' ');
+                text.push_str(seg.text);
+            } else if seg.syntax_type != SyntaxType::Blank && !seg.text.trim().is_empty() {
+                in_header = false;
+            }
+        })?;
+        let text = normalize(&text);
+        info.detected = KNOWN_LICENSES
+            .iter()
+            .filter(|(_, phrases)| phrases.iter().all(|p| text.contains(&normalize(p))))
+            .map(|(id, _)| *id)
+            .collect();
+        // A BSD-3-Clause header also contains the BSD-2-Clause text.
+        if info.detected.contains(&"BSD-3-Clause") {
+            info.detected.retain(|id| *id != "BSD-2-Clause");
+        }
+        Ok(info)
+    }
+}
+
+/// Lowercase the words of `text`, dropping punctuation and comment
+/// delimiters so that phrases match across wrapped comment lines.
+fn normalize(text: &str) -> String {
+    text.split(|c: char| c.is_whitespace() || matches!(c, ',' | ';' | ':' | '(' | ')'))
+        .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric()))
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_lowercase())
+        .join(" ")
+}
@@ -0,0 +1,66 @@
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use crate::{
+    error::CoreError,
+    parser::{match_keyword, CoreParser},
+};
+
+pub const DEFAULT_MARKERS: &[&str] = &["TODO", "FIXME", "XXX", "HACK"];
+
+/// A marker such as `TODO` found in a comment.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Marker {
+    pub file: Option<PathBuf>,
+    /// 1-based line number.
+    pub line: usize,
+    pub marker: String,
+    /// The text following the marker, up to the end of the comment line.
+    pub text: String,
+}
+
+impl CoreParser {
+    /// Collect `markers` found in comments.
+    ///
+    /// Markers are matched case-sensitively as whole words, so `TODO` inside a
+    /// string or an identifier like `TODO_LIST` is not reported. At most one
+    /// marker, the leftmost, is reported per comment line.
+    pub fn find_markers(&self, markers: &[&str]) -> Result<Vec<Marker>, CoreError> {
+        let closers: Vec<&str> = self
+            .syntax()
+            .comment_pairs
+            .iter()
+            .chain(self.syntax().doc_comment_pairs)
+            .map(|p| p.right)
+            .collect();
+        let mut found = Vec::new();
+        self.parse_with_segments(&mut |seg| {
+            if !seg.is_comment() {
+                return;
+            }
+            let Some((idx, marker)) = markers
+                .iter()
+                .filter(|m| !m.is_empty())
+                .filter_map(|m| match_keyword(seg.text, m).next().map(|idx| (idx, *m)))
+                .min_by_key(|(idx, _)| *idx)
+            else {
+                return;
+            };
+            let mut text = seg.text[idx + marker.len()..].trim_end();
+            if let Some(closer) = closers.iter().find(|c| text.ends_with(*c)) {
+                text = &text[..text.len() - closer.len()];
+            }
+            found.push(Marker {
+                file: self.path().map(|p| p.to_path_buf()),
+                line: seg.line,
+                marker: marker.to_string(),
+                text: text
+                    .trim_start_matches(|c: char| c == ':' || c.is_whitespace())
+                    .trim_end()
+                    .to_string(),
+            });
+        })?;
+        Ok(found)
+    }
+}
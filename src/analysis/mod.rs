@@ -0,0 +1,13 @@
+mod doc;
+mod license;
+mod marker;
+mod report;
+mod stats;
+mod strip;
+
+pub use doc::*;
+pub use license::*;
+pub use marker::*;
+pub use report::*;
+pub use stats::*;
+pub use strip::*;
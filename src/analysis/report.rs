@@ -0,0 +1,45 @@
+use std::path::PathBuf;
+
+use crate::{
+    error::CoreError,
+    parser::{CoreParser, ParseResult},
+};
+
+use super::{LicenseInfo, TextStats};
+
+/// Per-file report, holding the counting result and the optional analyses
+/// attached to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileReport {
+    pub file: Option<PathBuf>,
+    pub result: ParseResult,
+    pub license: Option<LicenseInfo>,
+    pub text: Option<TextStats>,
+}
+
+impl FileReport {
+    pub fn with_license(self, license: LicenseInfo) -> Self {
+        Self {
+            license: Some(license),
+            ..self
+        }
+    }
+
+    pub fn with_text_stats(self, text: TextStats) -> Self {
+        Self {
+            text: Some(text),
+            ..self
+        }
+    }
+}
+
+impl CoreParser {
+    pub fn report(&self) -> Result<FileReport, CoreError> {
+        Ok(FileReport {
+            file: self.path().map(|p| p.to_path_buf()),
+            result: self.parse()?,
+            license: None,
+            text: None,
+        })
+    }
+}
@@ -0,0 +1,95 @@
+use std::ops::{Add, AddAssign};
+
+use serde::Serialize;
+use unicode_width::UnicodeWidthChar;
+
+use crate::{
+    error::CoreError,
+    language::SyntaxType,
+    parser::{CoreParser, ParseResult},
+};
+
+/// Display columns a tab advances to the next multiple of.
+pub const TAB_WIDTH: usize = 4;
+
+/// Size and line-length statistics of a file, or of several files once
+/// summed up.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct TextStats {
+    /// Total size, including line endings.
+    pub bytes: usize,
+    pub code_bytes: usize,
+    pub comment_bytes: usize,
+    pub string_bytes: usize,
+    pub max_line_chars: usize,
+    pub max_line_columns: usize,
+    /// Number of lines wider than the configured width, in display columns.
+    pub long_lines: usize,
+}
+
+impl TextStats {
+    fn add_line(&mut self, line: &str, max_width: usize) {
+        let columns = display_columns(line);
+        self.max_line_chars = self.max_line_chars.max(line.chars().count());
+        self.max_line_columns = self.max_line_columns.max(columns);
+        if columns > max_width {
+            self.long_lines += 1;
+        }
+    }
+}
+
+impl Add for TextStats {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            bytes: self.bytes + rhs.bytes,
+            code_bytes: self.code_bytes + rhs.code_bytes,
+            comment_bytes: self.comment_bytes + rhs.comment_bytes,
+            string_bytes: self.string_bytes + rhs.string_bytes,
+            max_line_chars: self.max_line_chars.max(rhs.max_line_chars),
+            max_line_columns: self.max_line_columns.max(rhs.max_line_columns),
+            long_lines: self.long_lines + rhs.long_lines,
+        }
+    }
+}
+
+impl AddAssign for TextStats {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl CoreParser {
+    /// Parse the content and collect its [`TextStats`] in the same pass.
+    ///
+    /// Lines wider than `max_width` display columns are counted as long lines.
+    pub fn parse_with_stats(
+        &self,
+        max_width: usize,
+    ) -> Result<(ParseResult, TextStats), CoreError> {
+        let mut stats = TextStats::default();
+        let result = self.parse_with_segments(&mut |seg| {
+            let bytes = seg.text.len();
+            if seg.is_comment() {
+                stats.comment_bytes += bytes;
+            } else if seg.is_string() {
+                stats.string_bytes += bytes;
+            } else if seg.syntax_type == SyntaxType::Code {
+                stats.code_bytes += bytes;
+            }
+        })?;
+        stats.bytes = self.content().len();
+        for line in self.split_lines() {
+            stats.add_line(line, max_width);
+        }
+        Ok((result, stats))
+    }
+}
+
+/// Width of `line` in display columns, expanding tabs to [`TAB_WIDTH`].
+pub fn display_columns(line: &str) -> usize {
+    line.chars().fold(0, |columns, c| match c {
+        '\t' => (columns / TAB_WIDTH + 1) * TAB_WIDTH,
+        c => columns + c.width().unwrap_or(0),
+    })
+}
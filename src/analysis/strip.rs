@@ -0,0 +1,99 @@
+use std::ops::Range;
+
+use crate::{error::CoreError, language::SyntaxType, parser::CoreParser};
+
+/// Options of [`CoreParser::strip_comments`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StripOptions {
+    /// Also remove documentation comments.
+    pub doc: bool,
+    /// Drop the lines left blank by the removal instead of keeping them as
+    /// empty lines.
+    pub collapse_blank: bool,
+}
+
+impl CoreParser {
+    /// Return the content with its comments removed.
+    ///
+    /// Strings that merely look like comments are left untouched, and so are
+    /// doc strings. The code of docs, counted with
+    /// [`CoreParser::init_doc_code`], is removed with its doc comments only.
+    /// Unless blank lines are collapsed, the output keeps the line numbers of
+    /// the original content. Lines are joined with `'\n'`.
+    pub fn strip_comments(&self, options: &StripOptions) -> Result<String, CoreError> {
+        let lines: Vec<&str> = self.split_lines().collect();
+        let mut kept: Vec<String> = vec![String::new(); lines.len()];
+        // Byte ranges of the comments removed from each line.
+        let mut removed: Vec<Vec<Range<usize>>> = vec![Vec::new(); lines.len()];
+        // The code of docs goes with the doc comment on its line.
+        let mut doc_code: Vec<Vec<&str>> = vec![Vec::new(); lines.len()];
+        let mut doc_removed = vec![false; lines.len()];
+        let range = |idx: usize, text: &str| {
+            let start = (text.as_ptr() as usize).checked_sub(lines[idx].as_ptr() as usize)?;
+            Some(start..start + text.len())
+        };
+        self.parse_with_segments(&mut |seg| {
+            let idx = seg.line - 1;
+            if seg.doc_code {
+                doc_code[idx].push(seg.text);
+                return;
+            }
+            let remove = match seg.syntax_type {
+                SyntaxType::SimpleComment | SyntaxType::MultiComment => true,
+                SyntaxType::DocComment | SyntaxType::DocMultiComment => options.doc,
+                _ => false,
+            };
+            if !remove {
+                kept[idx].push_str(seg.text);
+                return;
+            }
+            doc_removed[idx] |= seg.is_doc();
+            removed[idx].extend(range(idx, seg.text));
+        })?;
+        for (idx, texts) in doc_code.into_iter().enumerate() {
+            for text in texts {
+                if doc_removed[idx] {
+                    removed[idx].extend(range(idx, text));
+                } else {
+                    kept[idx].push_str(text);
+                }
+            }
+        }
+
+        let mut output = Vec::with_capacity(lines.len());
+        for (idx, line) in lines.iter().enumerate() {
+            if removed[idx].is_empty() {
+                output.push(line.to_string());
+            } else if kept[idx].trim().is_empty() {
+                if !options.collapse_blank {
+                    output.push(String::new());
+                }
+            } else {
+                output.push(self.cut(line, &mut removed[idx]).trim_end().to_string());
+            }
+        }
+        let mut output = output.join("\n");
+        if self.content().ends_with('\n') && !output.is_empty() {
+            output.push('\n');
+        }
+        Ok(output)
+    }
+
+    /// `line` without the byte `ranges`, which are blanked out instead when
+    /// the columns of the line are significant, so that the text after them
+    /// stays in its columns.
+    fn cut(&self, line: &str, ranges: &mut [Range<usize>]) -> String {
+        ranges.sort_by_key(|range| range.start);
+        let mut text = String::with_capacity(line.len());
+        let mut end = 0;
+        for range in ranges.iter() {
+            text.push_str(&line[end..range.start]);
+            if self.syntax().code_columns.is_some() {
+                text.extend(line[range.clone()].chars().map(|_| ' '));
+            }
+            end = range.end;
+        }
+        text.push_str(&line[end..]);
+        text
+    }
+}
@@ -4,6 +4,12 @@ use thiserror::Error;
 pub enum CoreError {
     #[error("Syntax error: {0}")]
     SyntaxError(String),
+    #[error("Unknown language: {0}")]
+    UnknownLanguage(String),
+    #[error("Unsupported: {0}")]
+    Unsupported(String),
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
 }
 
 impl<T: std::fmt::Debug> From<nom::Err<T>> for CoreError {
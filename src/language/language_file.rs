@@ -1,19 +1,38 @@
-use std::path::Path;
+use std::path::{Path, MAIN_SEPARATOR};
 
-use regex::Regex;
+use crate::parser::{full_regex, search_regex};
 
 #[derive(Debug, Clone)]
 pub struct LanguageFile {
     pub extension: Option<FileItem>,
     pub file_name: Option<FileItem>,
+    /// Regular expressions translated from the path globs of the language,
+    /// as `.github/workflows/*.yml`. They match the path, with `/`
+    /// separators, or one of its trailing components.
+    pub path: &'static [&'static str],
 }
 
 impl LanguageFile {
     pub fn is_match_file(&self, file: &Path) -> bool {
-        self.is_match(
-            file.file_name().and_then(|e| e.to_str()),
-            file.extension().and_then(|e| e.to_str()),
-        )
+        self.is_match_path(file)
+            || self.is_match(
+                file.file_name().and_then(|e| e.to_str()),
+                file.extension().and_then(|e| e.to_str()),
+            )
+    }
+
+    /// Whether one of the path globs matches `file`.
+    pub fn is_match_path(&self, file: &Path) -> bool {
+        if self.path.is_empty() {
+            return false;
+        }
+        let Some(file) = file.to_str() else {
+            return false;
+        };
+        let file = file.replace(MAIN_SEPARATOR, "/");
+        self.path
+            .iter()
+            .any(|glob| search_regex(glob).is_match(&file))
     }
     pub fn is_match(&self, file_name: Option<&str>, ext: Option<&str>) -> bool {
         self.is_match_file_name(file_name) || self.is_match_extension(ext)
@@ -45,7 +64,9 @@ impl LanguageFile {
 
 #[derive(Debug, Clone)]
 pub struct FileItem {
-    pub regex: &'static [Regex],
+    /// Regular expressions matching the whole text, compiled once on first
+    /// use.
+    pub regex: &'static [&'static str],
     pub case_insensitive: &'static [&'static str],
     pub plain: &'static [&'static str],
 }
@@ -56,6 +77,6 @@ impl FileItem {
             || self
                 .case_insensitive
                 .contains(&content.to_ascii_lowercase().as_str())
-            || self.regex.iter().any(|r| r.is_match(content))
+            || self.regex.iter().any(|r| full_regex(r).is_match(content))
     }
 }
@@ -21,21 +21,171 @@ pub struct SyntaxPair {
     pub name: &'static str,
     pub left: &'static str,
     pub right: &'static str,
+    /// Only for comments: the comment may contain nested comments, opened by
+    /// the nested pairs sharing its closing delimiter.
+    pub nested: bool,
+    /// Only for strings: how the closing delimiter is kept from ending the
+    /// string.
+    pub escape: Escape,
+    /// Only for strings: the string may span lines. Otherwise an unclosed
+    /// string ends with its line.
+    pub multiline: bool,
+}
+
+/// How the closing delimiter of a string is written inside it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Escape {
+    /// A backslash escapes the next character, as in `"a\"b"`.
+    Backslash,
+    /// The closing delimiter is written twice, as in SQL `'it''s'`.
+    Doubled,
+    /// Nothing escapes the closing delimiter.
+    None,
+}
+
+/// How a line lacking the `line_prefix` of its language is counted. Blank
+/// lines are always counted as blank.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MissingPrefix {
+    /// The prefix is optional: the line is parsed as it is.
+    Code,
+    /// The line is prose, counted as a doc comment.
+    Prose,
+    /// The line is counted as blank.
+    Blank,
+    /// The line is a syntax error.
+    Error,
+}
+
+/// A pair whose closing delimiter depends on the opening one, such as a
+/// heredoc.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapturedPair {
+    pub name: &'static str,
+    /// Regular expression matching the opening delimiter.
+    pub left: &'static str,
+    /// Template of the closing delimiter, where `$name` expands to the text
+    /// captured by the group `name` of `left`.
+    pub right: &'static str,
+    /// The region starts on the next line and ends with a line made of the
+    /// closing delimiter alone.
+    pub next_line: bool,
+    /// The region may run to the end of the input without being closed, as
+    /// PHP code at the end of a file.
+    pub open_end: bool,
+    /// The opening delimiter only matches at the beginning of a line, after
+    /// its indentation.
+    pub line_start: bool,
+    /// Like `line_start`, and the previous line must be blank, as for the
+    /// indented code blocks of Markdown.
+    pub after_blank: bool,
+    /// Like `line_start`, with no indentation before the opening delimiter,
+    /// as the `=begin` of Ruby.
+    pub unindented: bool,
+    /// The region ends before the first non-blank line indented no deeper
+    /// than the column of the opening delimiter; `right` is not used.
+    pub indented: bool,
+    /// The region ends with the first line not ending with a backslash, as
+    /// the `RUN` instructions of a Dockerfile; `right` is not used.
+    pub continued: bool,
+    /// Only for strings with `next_line`: the closing delimiter may be
+    /// followed on its line by anything but an identifier character, as
+    /// `EOT;` closing a PHP heredoc.
+    pub flexible: bool,
+    /// Only for strings closed on their line or later: how the closing
+    /// delimiter is escaped inside them.
+    pub escape: Escape,
+}
+
+/// A sub language delimited by a [`CapturedPair`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapturedSubLanguage {
+    pub pair: CapturedPair,
+    /// Language of the region, unless `language_group` names another one.
+    pub language: LanguageType,
+    /// Group of `pair.left` whose text names the language of the region, such
+    /// as the info string of a Markdown code fence.
+    pub language_group: Option<&'static str>,
+}
+
+/// A rule counting the content of a string as another language, as the
+/// language injections of an IDE. Each pattern given must match.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Injection {
+    /// Regular expression searched in a comment ending on the line of the
+    /// string or on the line before, as `language=sql`.
+    pub comment: Option<&'static str>,
+    /// Regular expression searched in the code before the string on its
+    /// line, as a call to `sqlx::query!`.
+    pub before: Option<&'static str>,
+    /// Language of the string, unless `language_group` names another one.
+    pub language: LanguageType,
+    /// Group of the patterns whose text names the language of the string.
+    pub language_group: Option<&'static str>,
 }
 
 #[derive(Debug, Clone)]
 pub struct LanguageSyntax {
-    pub line_prefix: Option<&'static str>,
+    /// Prefixes of the lines of the program, stripped before parsing.
+    pub line_prefix: &'static [&'static str],
+    /// Whitespace may come before the `line_prefix`.
     pub ignore_prefix_space: bool,
+    /// How the lines lacking a `line_prefix` are counted, outside of literate
+    /// mode.
+    pub missing_prefix: MissingPrefix,
+    /// Text is prose, counted as doc comments, except for the lines starting
+    /// with `line_prefix`, the blocks of `literate_code_pairs` and the sub
+    /// language regions, as in literate Haskell. Without code of its own, the
+    /// comments of the language are those of the prose, as in Markdown.
+    pub literate: bool,
+    /// The 1-based, inclusive range of columns holding the program, as in
+    /// fixed-form Fortran or COBOL. Text outside of it is ignored.
+    pub code_columns: Option<(usize, usize)>,
     pub(crate) doc_comment: &'static [&'static str],
     pub(crate) simple_comment: &'static [&'static str],
     pub(crate) block_pairs: &'static [SyntaxPair],
     pub(crate) doc_comment_pairs: &'static [SyntaxPair],
     pub(crate) comment_pairs: &'static [SyntaxPair],
+    /// Block comments whose closing delimiter depends on the opening one, as
+    /// the long comments of Lua.
+    pub(crate) captured_comment_pairs: &'static [CapturedPair],
+    /// Doc comments whose closing delimiter depends on the opening one, as
+    /// the POD blocks of Perl.
+    pub(crate) captured_doc_comment_pairs: &'static [CapturedPair],
+    /// Lines opening and closing a block of code in literate mode, as
+    /// `\begin{code}` and `\end{code}`.
+    pub(crate) literate_code_pairs: &'static [CapturedPair],
     pub(crate) literal_quote_pairs: &'static [SyntaxPair],
     pub(crate) quote_pairs: &'static [SyntaxPair],
+    pub(crate) captured_quote_pairs: &'static [CapturedPair],
     pub(crate) doc_quote_pairs: &'static [SyntaxPair],
     pub(crate) sublang_pairs: &'static [(SyntaxPair, LanguageType)],
+    pub(crate) captured_sublang_pairs: &'static [CapturedSubLanguage],
+    pub(crate) injections: &'static [Injection],
+    /// Code in doc comments and doc strings, matched at the start of their
+    /// text. With `next_line`, a block runs until a line made of the closing
+    /// delimiter alone, as the fenced examples of Rust. Otherwise the rest of
+    /// the line is code, and so are the next lines starting with `left` or
+    /// `right`, as the `>>>` and `...` prompts of Python doctests.
+    pub(crate) doc_code_pairs: &'static [CapturedSubLanguage],
+    pub(crate) complexity_keywords: &'static [&'static str],
+    pub(crate) preprocessor: &'static [&'static str],
+    /// Markers making a whole line a comment when found at their 1-based
+    /// column, as `*` in column 7 of COBOL.
+    pub(crate) column_comment: &'static [(usize, &'static str)],
+    /// Prefixes commenting out the next datum, as `#;` in Scheme.
+    pub(crate) datum_comment: &'static [&'static str],
+    /// Template language layered over this one, whose tags are counted as
+    /// that language wherever the code of this one may hold them.
+    pub template: Option<LanguageType>,
+    /// Tags of a template language, as `{% %}` and `{# #}` in Jinja. They
+    /// are parsed with their delimiters, so that the comment pairs of the
+    /// template language tell comments apart.
+    pub(crate) template_tags: &'static [SyntaxPair],
+    /// Simple comments only start at the beginning of a word, as `#` in shell.
+    pub(crate) single_word_start: bool,
+    /// Lines after which the rest of the file is data, such as `__END__`.
+    pub(crate) end_comment: &'static [&'static str],
 }
 
 impl LanguageSyntax {
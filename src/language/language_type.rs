@@ -1,9 +1,40 @@
-use serde::{Deserialize, Serialize};
+use std::path::Path;
 
-use super::{LanguageSyntax, SyntaxPair, LanguageFile, FileItem};
+use serde::{
+    de::{value::StrDeserializer, IntoDeserializer},
+    Deserialize, Serialize,
+};
+
+use super::{
+    CapturedPair, CapturedSubLanguage, Escape, FileItem, Injection, LanguageFile, LanguageSyntax,
+    MissingPrefix, SyntaxPair,
+};
 
 include!(concat!(env!("OUT_DIR"), "/language_syntax_tera.rs"));
 
+impl LanguageType {
+    /// Detect the language of a file from its path. The path globs of a
+    /// language win over the file names and extensions of the others.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let files = Self::all_language_file();
+        files
+            .iter()
+            .find(|(_, file)| file.is_match_path(path))
+            .or_else(|| files.iter().find(|(_, file)| file.is_match_file(path)))
+            .map(|(lang, _)| *lang)
+    }
+
+    /// Find a language by its identifier, name or one of its aliases, trying
+    /// the lowercase form of `name` as well.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let find = |name: &str| {
+            let de: StrDeserializer<serde::de::value::Error> = name.into_deserializer();
+            Self::deserialize(de).ok()
+        };
+        find(name).or_else(|| find(&name.to_lowercase()))
+    }
+}
+
 /*
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Hash)]
 pub enum LanguageType {
@@ -15,8 +46,9 @@ impl LanguageType {
     pub const fn get_language_syntax(&self) -> LanguageSyntax {
         match self {
             Self::Rust => LanguageSyntax {
-                line_prefix: None,
+                line_prefix: &[],
                 ignore_prefix_space: true,
+                missing_prefix: MissingPrefix::Prose,
                 block_pairs: &[SyntaxPair {
                     name: "simple",
                     left: "{",
@@ -25,7 +57,8 @@ impl LanguageType {
                 comment_pairs: &[SyntaxPair {
                     name: "simple",
                     left: "/*",
-                    right: "*/",
+                    right: "*/
+",
                 }],
                 quote_pairs: &[SyntaxPair {
                     name: "simple",
@@ -69,4 +102,4 @@ impl LanguageType {
         }
     }
 }
-*/
\ No newline at end of file
+*/
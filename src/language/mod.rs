@@ -1,7 +1,7 @@
-mod language_type;
-mod language_syntax;
 mod language_file;
+mod language_syntax;
+mod language_type;
 
-pub use language_type::*;
-pub use language_syntax::*;
 pub use language_file::*;
+pub use language_syntax::*;
+pub use language_type::*;
@@ -1,10 +1,12 @@
 mod parse;
 mod result;
+mod segment;
 mod state;
 mod utils;
 
 pub use parse::*;
 pub use result::*;
+pub use segment::*;
 pub use state::*;
 pub use utils::*;
 
@@ -1,59 +1,139 @@
-use std::collections::BTreeMap;
-
-use nom::{
-    branch::alt,
-    character::complete::anychar,
-    combinator::{eof, map_res},
-    multi::many_till,
-};
+use nom::{branch::alt, character::complete::anychar, combinator::map_res, multi::many_till};
 
 use crate::{
-    language::LanguageSyntax,
+    language::{LanguageSyntax, SyntaxPair},
     parser::{tag_all, NomError},
 };
 
+/// Find the end of the comment whose open pairs are on `stack`, innermost
+/// last, returning the rest of the line after it, or `None` if the comment
+/// goes on to the next line.
+///
+/// A comment ends at the first closing delimiter of its pair, unless the pair
+/// is nested, in which case every nested pair sharing that delimiter opens
+/// another level.
 pub fn multi_comment<'a>(
-    leading_map: &mut BTreeMap<&'a str, usize>,
-    trailing_map: &mut BTreeMap<&'a str, usize>,
+    stack: &mut Vec<&'static SyntaxPair>,
     syntax: &LanguageSyntax,
     line: &'a str,
 ) -> Option<&'a str> {
     let mut line = line;
     loop {
-        if leading_map.values().sum::<usize>() == trailing_map.values().sum::<usize>() {
+        let Some(&inner) = stack.last() else {
             break Some(line);
-        }
-        if line.is_empty() {
-            break None;
-        } else if let Ok((rest, (_, (tagging, flag)))) = many_till(
+        };
+        let openers: Vec<&'static SyntaxPair> = if inner.nested {
+            syntax
+                .doc_comment_pairs
+                .iter()
+                .chain(syntax.comment_pairs)
+                .filter(|p| p.nested && p.right == inner.right)
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let found = many_till(
             anychar,
             alt((
-                map_res(tag_all(syntax.doc_comment_pairs, |p| p.right), |r| {
-                    Ok::<_, NomError>((r.right, false))
-                }),
-                map_res(tag_all(syntax.comment_pairs, |p| p.right), |r| {
-                    Ok::<_, NomError>((r.right, false))
-                }),
-                map_res(tag_all(syntax.doc_comment_pairs, |p| p.left), |r| {
-                    Ok::<_, NomError>((r.left, true))
-                }),
-                map_res(tag_all(syntax.comment_pairs, |p| p.left), |r| {
-                    Ok::<_, NomError>((r.left, true))
+                map_res(tag_all(&[inner], |p| p.right), |_| Ok::<_, NomError>(None)),
+                map_res(tag_all(&openers, |p| p.left), |p| {
+                    Ok::<_, NomError>(Some(*p))
                 }),
-                map_res(eof, |_| Ok::<_, NomError>(("", false))),
             )),
-        )(line)
-        {
-            line = rest;
-            if tagging.is_empty() {
-                continue;
-            } else if flag {
-                *leading_map.entry(tagging).or_insert(0) += 1;
-                continue;
-            } else {
-                *trailing_map.entry(tagging).or_insert(0) += 1;
+        )(line);
+        match found {
+            Ok((rest, (_, Some(pair)))) => {
+                stack.push(pair);
+                line = rest;
+            }
+            Ok((rest, (_, None))) => {
+                stack.pop();
+                line = rest;
+            }
+            Err(_) => break None,
+        }
+    }
+}
+
+/// End of a block comment.
+pub enum CommentEnd {
+    /// The open pairs of the comment, innermost last.
+    Pairs(Vec<&'static SyntaxPair>),
+    /// A closing delimiter captured from the opening one.
+    Captured(String),
+    /// The end of the datum commented out, as by `#;` in Scheme.
+    Datum(Datum),
+}
+
+impl CommentEnd {
+    pub fn find<'a>(&mut self, syntax: &LanguageSyntax, line: &'a str) -> Option<&'a str> {
+        match self {
+            Self::Pairs(stack) => multi_comment(stack, syntax, line),
+            Self::Captured(closer) => line
+                .find(closer.as_str())
+                .map(|idx| &line[idx + closer.len()..]),
+            Self::Datum(datum) => datum.find(line),
+        }
+    }
+}
+
+/// Progress through a datum of a Lisp, which is an atom, a string or a list.
+#[derive(Debug, Default)]
+pub struct Datum {
+    depth: usize,
+    started: bool,
+    in_string: bool,
+}
+
+impl Datum {
+    /// Return the rest of the line after the datum, or `None` if it goes on
+    /// to the next line.
+    fn find<'a>(&mut self, line: &'a str) -> Option<&'a str> {
+        let mut chars = line.char_indices();
+        while let Some((idx, c)) = chars.next() {
+            if self.in_string {
+                match c {
+                    '\\' => {
+                        chars.next();
+                    }
+                    '"' => {
+                        self.in_string = false;
+                        if self.depth == 0 {
+                            return Some(&line[idx + 1..]);
+                        }
+                    }
+                    _ => {}
+                }
                 continue;
             }
+            match c {
+                '"' => {
+                    self.started = true;
+                    self.in_string = true;
+                }
+                '(' | '[' | '{' => {
+                    self.started = true;
+                    self.depth += 1;
+                }
+                ')' | ']' | '}' => {
+                    if self.depth == 0 {
+                        return Some(&line[idx..]);
+                    }
+                    self.depth -= 1;
+                    if self.depth == 0 {
+                        return Some(&line[idx + 1..]);
+                    }
+                }
+                c if c.is_whitespace() => {
+                    if self.started && self.depth == 0 {
+                        return Some(&line[idx..]);
+                    }
+                }
+                // Reader prefixes, as in `'(a b)` or `#(1 2)`.
+                '\'' | '`' | ',' | '@' | '#' if !self.started => {}
+                _ => self.started = true,
+            }
         }
+        (self.started && self.depth == 0 && !self.in_string).then_some("")
     }
 }
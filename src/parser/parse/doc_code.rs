@@ -0,0 +1,164 @@
+use crate::{
+    error::CoreError,
+    language::{CapturedSubLanguage, LanguageSyntax, LanguageType},
+    parser::{consumed, match_captured, LinePosition, ParseResult, Segment},
+};
+
+use super::{sublang::sub_language_of, CoreParser};
+
+/// Code found in the doc comments and doc strings of a file, as the examples
+/// of Rust or the doctests of Python.
+#[derive(Debug)]
+pub struct DocCode<'a> {
+    /// Count the code as its language, see [`CoreParser::init_doc_code`].
+    enabled: bool,
+    block: Option<Block<'a>>,
+}
+
+#[derive(Debug)]
+struct Block<'a> {
+    language: LanguageType,
+    end: BlockEnd,
+    /// Line of the first code line.
+    first_line: usize,
+    /// Latest line of the block, delimiters included.
+    last_line: usize,
+    lines: Vec<&'a str>,
+}
+
+#[derive(Debug)]
+enum BlockEnd {
+    /// A line made of the closing delimiter alone.
+    Delimiter(String),
+    /// The first line starting with no prompt of the rule.
+    Prompt(&'static CapturedSubLanguage),
+}
+
+impl<'a> DocCode<'a> {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            block: None,
+        }
+    }
+
+    /// Feed the `text` of doc line `line_no`, without its comment marker,
+    /// unless the code of docs is not counted.
+    ///
+    /// Returns the code on the line, which the caller must not count, as the
+    /// block it belongs to is counted when it ends.
+    pub fn line(
+        &mut self,
+        syntax: &LanguageSyntax,
+        text: &'a str,
+        line_no: usize,
+        result: &mut ParseResult,
+        sink: &mut dyn FnMut(Segment<'a>),
+    ) -> Result<Option<&'a str>, CoreError> {
+        if !self.enabled {
+            return Ok(None);
+        }
+        if self
+            .block
+            .as_ref()
+            .is_some_and(|block| block.last_line + 1 != line_no)
+        {
+            self.finish(result, sink)?;
+        }
+        if let Some(block) = &mut self.block {
+            let code = match &block.end {
+                BlockEnd::Delimiter(closer) => (text.trim() != closer).then_some(text),
+                BlockEnd::Prompt(rule) => prompted(rule, text),
+            };
+            if let Some(code) = code {
+                block.push(code, line_no);
+                return Ok(Some(code));
+            }
+            let fenced = matches!(block.end, BlockEnd::Delimiter(_));
+            self.finish(result, sink)?;
+            if fenced {
+                // The closing delimiter stays in the doc.
+                return Ok(None);
+            }
+        }
+
+        let Some((code, rule, closer)) = match_captured(
+            syntax.doc_code_pairs,
+            |p| &p.pair,
+            text,
+            LinePosition::Start,
+        ) else {
+            return Ok(None);
+        };
+        let language = sub_language_of(rule, consumed(text, code));
+        if rule.pair.next_line {
+            self.block = Some(Block {
+                language,
+                end: BlockEnd::Delimiter(closer),
+                first_line: line_no + 1,
+                last_line: line_no,
+                lines: Vec::new(),
+            });
+            return Ok(None);
+        }
+        self.block = Some(Block {
+            language,
+            end: BlockEnd::Prompt(rule),
+            first_line: line_no,
+            last_line: line_no,
+            lines: vec![code],
+        });
+        Ok(Some(code))
+    }
+
+    /// Count the pending block, if any, as its language.
+    pub fn finish(
+        &mut self,
+        result: &mut ParseResult,
+        sink: &mut dyn FnMut(Segment<'a>),
+    ) -> Result<(), CoreError> {
+        let Some(block) = self.block.take() else {
+            return Ok(());
+        };
+        if block.lines.is_empty() {
+            return Ok(());
+        }
+        let syntax = block.language.get_language_syntax();
+        let offset = block.first_line - 1;
+        let part = CoreParser::walk_lines(block.lines.into_iter(), &syntax, &mut |seg| {
+            sink(Segment {
+                line: seg.line + offset,
+                sub_language: seg.sub_language.or(Some(block.language)),
+                doc_code: true,
+                ..seg
+            })
+        })?;
+        result.join((block.language, part));
+        Ok(())
+    }
+}
+
+impl<'a> Block<'a> {
+    fn push(&mut self, code: &'a str, line_no: usize) {
+        self.lines.push(code);
+        self.last_line = line_no;
+    }
+}
+
+/// Code after a prompt of `rule` starting `text`, where `right` is the
+/// prompt continuing a block, as `...` in a doctest.
+fn prompted<'a>(rule: &CapturedSubLanguage, text: &'a str) -> Option<&'a str> {
+    if let Some((code, _, _)) = match_captured(
+        std::slice::from_ref(rule),
+        |p| &p.pair,
+        text,
+        LinePosition::Start,
+    ) {
+        return Some(code);
+    }
+    if rule.pair.right.is_empty() {
+        return None;
+    }
+    let code = text.trim_start().strip_prefix(rule.pair.right)?;
+    Some(code.strip_prefix(' ').unwrap_or(code))
+}
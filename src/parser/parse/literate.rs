@@ -0,0 +1,73 @@
+use crate::{
+    language::LanguageSyntax,
+    parser::{match_captured, LinePosition},
+};
+
+use super::strip_line_prefix;
+
+/// What a line of a literate program holds.
+pub enum LiterateLine<'a> {
+    /// Code, or a blank line, left to the parser without its `line_prefix`.
+    Code(&'a str),
+    /// A line opening or closing a block of code.
+    Delimiter,
+    Prose,
+}
+
+/// State of a literate program between its lines.
+#[derive(Debug, Default)]
+pub struct Literate {
+    /// Closing line of the block of code the current line is in.
+    block: Option<String>,
+}
+
+impl Literate {
+    /// Classify a whole `line` of a literate program, starting at `position`.
+    pub fn line<'a>(
+        &mut self,
+        line: &'a str,
+        syntax: &LanguageSyntax,
+        position: LinePosition,
+    ) -> LiterateLine<'a> {
+        if let Some(closer) = &self.block {
+            if line.trim() == closer {
+                self.block = None;
+                return LiterateLine::Delimiter;
+            }
+            return LiterateLine::Code(line);
+        }
+        if let Some(code) = strip_line_prefix(line, syntax) {
+            return LiterateLine::Code(code);
+        }
+        if let Some((_, _, closer)) =
+            match_captured(syntax.literate_code_pairs, |p| p, line, position)
+        {
+            self.block = Some(closer);
+            return LiterateLine::Delimiter;
+        }
+        let indented = line.trim_start();
+        if line.trim().is_empty()
+            || [line, indented].into_iter().any(|text| {
+                match_captured(syntax.captured_sublang_pairs, |p| &p.pair, text, position).is_some()
+            })
+        {
+            return LiterateLine::Code(line);
+        }
+        // Without code of its own, the comments of the language are those of
+        // the prose, as HTML comments in Markdown, and left to the parser.
+        let prose_comments = syntax.line_prefix.is_empty() && syntax.literate_code_pairs.is_empty();
+        if prose_comments
+            && (syntax
+                .simple_comment
+                .iter()
+                .any(|c| indented.starts_with(c))
+                || syntax
+                    .comment_pairs
+                    .iter()
+                    .any(|p| indented.starts_with(p.left)))
+        {
+            return LiterateLine::Code(line);
+        }
+        LiterateLine::Prose
+    }
+}
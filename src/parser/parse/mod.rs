@@ -1,10 +1,18 @@
 #![allow(unused_assignments)]
 
 mod comment;
+mod doc_code;
+mod literate;
+mod notebook;
 mod string;
 mod sublang;
 
-use std::collections::BTreeMap;
+use std::{
+    cell::Cell,
+    collections::VecDeque,
+    fs,
+    path::{Path, PathBuf},
+};
 
 use nom::{
     branch::alt,
@@ -18,56 +26,197 @@ use nom::{
 
 use crate::{
     error::CoreError,
-    language::{LanguageSyntax, LanguageType, SyntaxType},
+    language::{CapturedPair, LanguageSyntax, LanguageType, MissingPrefix, SyntaxType},
     parser::NomError,
 };
 
 use self::{
-    comment::multi_comment,
-    string::{litral_string, normal_string},
-    sublang::split_sublang_part,
+    comment::{CommentEnd, Datum},
+    doc_code::DocCode,
+    literate::{Literate, LiterateLine},
+    string::{string_end, LiteralEnd},
+    sublang::{injected_language, split_sublang_part, sub_language_of, Region},
 };
 
-use super::{tag_all, ParseResult};
+use super::{
+    at_column, captured_tag, column_range, consumed, count_keywords, match_captured, tag_all,
+    LinePosition, ParseResult, Segment,
+};
 
 #[derive(Debug, Clone)]
 pub struct CoreParser {
     content: String,
+    path: Option<PathBuf>,
+    language: LanguageType,
     syntax: LanguageSyntax,
+    doc_code: bool,
 }
 
 impl CoreParser {
     pub fn from_lang(lang: &LanguageType) -> Self {
         Self {
             content: String::new(),
+            path: None,
+            language: *lang,
             syntax: lang.get_language_syntax(),
+            doc_code: false,
         }
     }
 
+    /// Parse `host` with the tags of `template` layered over it, as HTML
+    /// rendered by Jinja.
+    pub fn from_template(host: &LanguageType, template: &LanguageType) -> Self {
+        let mut parser = Self::from_lang(host);
+        parser.syntax.template = Some(*template);
+        parser
+    }
+
+    /// Detect the language of the file and load its content.
+    ///
+    /// The extension of a template language is peeled off to find the host
+    /// language, so that `page.html.j2` is HTML layered with Jinja.
+    pub fn from_path(path: &Path) -> Result<Self, CoreError> {
+        let lang = LanguageType::from_path(path)
+            .ok_or_else(|| CoreError::UnknownLanguage(path.display().to_string()))?;
+        let host = if lang.get_language_syntax().template_tags.is_empty() {
+            None
+        } else {
+            path.file_stem()
+                .map(Path::new)
+                .and_then(LanguageType::from_path)
+        };
+        let mut parser = match host {
+            Some(host) => Self::from_template(&host, &lang),
+            None => Self::from_lang(&lang),
+        };
+        parser.init_content(&fs::read_to_string(path)?);
+        parser.init_path(path);
+        Ok(parser)
+    }
+
     pub fn init_content(&mut self, content: &str) {
         self.content = content.to_string();
     }
 
+    pub fn init_path(&mut self, path: &Path) {
+        self.path = Some(path.to_path_buf());
+    }
+
+    /// Count the code blocks of doc comments and doc strings as their
+    /// language, as the examples of Rust docs. Off by default.
+    pub fn init_doc_code(&mut self, count: bool) {
+        self.doc_code = count;
+    }
+
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    pub fn language(&self) -> LanguageType {
+        self.language
+    }
+
+    pub fn syntax(&self) -> &LanguageSyntax {
+        &self.syntax
+    }
+
     pub fn split_lines(&self) -> impl Iterator<Item = &str> {
         self.content.split_terminator('\n').map(|line| {
-            let Some(line) = line.strip_suffix('\r') else { return line };
-            let Some(line) = line.strip_suffix('\n') else { return line };
+            let Some(line) = line.strip_suffix('\r') else {
+                return line;
+            };
+            let Some(line) = line.strip_suffix('\n') else {
+                return line;
+            };
             line
         })
     }
 
+    /// Parse the content. Jupyter notebooks are counted cell by cell, see
+    /// [`CoreParser::parse_notebook`].
     pub fn parse(&self) -> Result<ParseResult, CoreError> {
-        Self::parse_lines(self.split_lines(), &self.syntax)
+        if self.language == LanguageType::Jupyter {
+            return Self::parse_notebook(&self.content);
+        }
+        Self::walk(self.split_lines(), &self.syntax, self.doc_code, &mut |_| {})
     }
 
     pub fn parse_lines<'a>(
         lines: impl Iterator<Item = &'a str>,
         syntax: &LanguageSyntax,
+    ) -> Result<ParseResult, CoreError> {
+        Self::walk_lines(lines, syntax, &mut |_| {})
+    }
+
+    /// Parse the content, feeding every classified piece of it to `sink`.
+    ///
+    /// Notebooks are not supported, as their cells are not slices of the
+    /// content.
+    pub fn parse_with_segments<'a>(
+        &'a self,
+        sink: &mut dyn FnMut(Segment<'a>),
+    ) -> Result<ParseResult, CoreError> {
+        if self.language == LanguageType::Jupyter {
+            return Err(CoreError::Unsupported(
+                "segments of Jupyter notebooks".to_string(),
+            ));
+        }
+        Self::walk(self.split_lines(), &self.syntax, self.doc_code, sink)
+    }
+
+    pub fn segments(&self) -> Result<Vec<Segment<'_>>, CoreError> {
+        let mut segments = Vec::new();
+        self.parse_with_segments(&mut |seg| segments.push(seg))?;
+        Ok(segments)
+    }
+
+    pub fn walk_lines<'a>(
+        lines: impl Iterator<Item = &'a str>,
+        syntax: &LanguageSyntax,
+        sink: &mut dyn FnMut(Segment<'a>),
+    ) -> Result<ParseResult, CoreError> {
+        Self::walk(lines, syntax, false, sink)
+    }
+
+    /// [`CoreParser::walk_lines`], counting the code of docs as its language
+    /// with `doc_code`.
+    fn walk<'a>(
+        lines: impl Iterator<Item = &'a str>,
+        syntax: &LanguageSyntax,
+        doc_code: bool,
+        sink: &mut dyn FnMut(Segment<'a>),
     ) -> Result<ParseResult, CoreError> {
         let mut result = ParseResult::default();
-        let mut lines = lines;
+        let mut lines = lines.peekable();
+        let mut line_no = 0;
         let mut trailing_line: Option<&str> = None;
         let mut prev_is_code = false;
+        // Code count when the current preprocessor directive started.
+        let mut directive: Option<usize> = None;
+        let mut directive_continues = false;
+        // Closing delimiters of the heredocs opened on the current line.
+        let mut heredocs: VecDeque<(String, &CapturedPair)> = VecDeque::new();
+        // Last line of the latest sub-language region, if that region counted it.
+        let mut sublang_line: Option<usize> = None;
+        // Latest blank line, where 0 stands for the start of the input.
+        let mut last_blank = 0;
+        let mut literate = Literate::default();
+        let mut doc_code = DocCode::new(doc_code);
+        // Latest comment of the host language, with the line it ends on.
+        let annotation = Cell::new(None);
+        let sink = &mut |seg: Segment<'a>| {
+            if seg.sub_language.is_none() && seg.is_comment() {
+                annotation.set(Some((seg.line, seg.text)));
+            }
+            sink(seg)
+        };
+        let template_tags = syntax.template.map_or(&[][..], |template| {
+            template.get_language_syntax().template_tags
+        });
         loop {
             let (line, mut is_newline) = if let Some(t) = trailing_line {
                 trailing_line = None;
@@ -76,42 +225,178 @@ impl CoreParser {
                     continue;
                 }
                 (t, false)
+            } else if let Some((closer, pair)) = heredocs.pop_front() {
+                'heredoc: loop {
+                    if let Some(body_line) = lines.next() {
+                        result.all += 1;
+                        line_no += 1;
+                        result.code += 1;
+                        sink(Segment::new(SyntaxType::LitString, line_no, body_line).continued());
+                        if closing_line(body_line, &closer, pair.flexible) {
+                            break 'heredoc;
+                        }
+                    } else if pair.open_end {
+                        break 'heredoc;
+                    } else {
+                        return Err(CoreError::SyntaxError(
+                            "No Heredoc Ending found.".to_string(),
+                        ));
+                    }
+                }
+                continue;
             } else if let Some(t) = lines.next() {
                 result.all += 1;
+                line_no += 1;
                 prev_is_code = false;
+                finish_directive(&mut result, directive.take());
+                if directive_continues || is_directive(t, syntax) {
+                    directive = Some(result.code);
+                    directive_continues = t.trim_end().ends_with('\\');
+                }
                 (t, true)
             } else {
+                finish_directive(&mut result, directive.take());
+                doc_code.finish(&mut result, sink)?;
                 break;
             };
 
-            let line = if is_newline {
-                if let Some(pf) = syntax.line_prefix {
-                    let (input, _) = tag::<_, _, NomError>(pf)(line)?;
-                    input
+            let line = if is_newline && syntax.literate {
+                let position = if last_blank + 1 == line_no {
+                    LinePosition::AfterBlank
                 } else {
-                    line
+                    LinePosition::Start
+                };
+                match literate.line(line, syntax, position) {
+                    LiterateLine::Code(code) => code,
+                    LiterateLine::Delimiter => {
+                        result.code += 1;
+                        sink(Segment::new(SyntaxType::Code, line_no, line));
+                        continue;
+                    }
+                    LiterateLine::Prose => {
+                        result.comment.doc += 1;
+                        sink(Segment::new(SyntaxType::DocComment, line_no, line));
+                        continue;
+                    }
+                }
+            } else if is_newline && !syntax.line_prefix.is_empty() && !line.trim().is_empty() {
+                match strip_line_prefix(line, syntax) {
+                    Some(code) => code,
+                    None => match syntax.missing_prefix {
+                        MissingPrefix::Code => line,
+                        MissingPrefix::Prose => {
+                            result.comment.doc += 1;
+                            sink(Segment::new(SyntaxType::DocComment, line_no, line));
+                            continue;
+                        }
+                        MissingPrefix::Blank => {
+                            result.blank += 1;
+                            last_blank = line_no;
+                            sink(Segment::new(SyntaxType::Blank, line_no, line));
+                            continue;
+                        }
+                        MissingPrefix::Error => {
+                            return Err(CoreError::SyntaxError(format!(
+                                "Missing line prefix on line {}.",
+                                line_no
+                            )));
+                        }
+                    },
                 }
             } else {
                 line
             };
 
+            let (line, column_comment) = match syntax.code_columns {
+                Some(columns) if is_newline => (
+                    column_range(line, columns),
+                    at_column(line, syntax.column_comment),
+                ),
+                _ => (line, is_newline && at_column(line, syntax.column_comment)),
+            };
+            if column_comment {
+                result.comment.normal += 1;
+                sink(Segment::new(SyntaxType::SimpleComment, line_no, line));
+                continue;
+            }
+
+            if is_newline && syntax.end_comment.contains(&line.trim_end()) {
+                // The rest of the file is data, as after `__END__` in Perl.
+                result.comment.normal += 1;
+                sink(Segment::new(SyntaxType::MultiComment, line_no, line));
+                for data_line in lines.by_ref() {
+                    result.all += 1;
+                    line_no += 1;
+                    result.comment.normal += 1;
+                    sink(Segment::new(SyntaxType::MultiComment, line_no, data_line).continued());
+                }
+                continue;
+            }
+
             if line.is_empty() || tuple((space0::<_, NomError>, eof))(line).is_ok() {
+                sink(Segment::new(SyntaxType::Blank, line_no, line));
                 if !prev_is_code && is_newline {
                     result.blank += 1;
                 }
+                if is_newline {
+                    last_blank = line_no;
+                }
             } else {
+                let position = if !is_newline {
+                    LinePosition::Inside
+                } else if last_blank + 1 == line_no {
+                    LinePosition::AfterBlank
+                } else {
+                    LinePosition::Start
+                };
                 let parsed: IResult<&str, (Vec<char>, SyntaxType)> = many_till(
                     anychar,
                     peek(alt((
+                        map_res(tag_all(template_tags, |p| p.left), |_| {
+                            Ok::<_, NomError>(SyntaxType::SubLanguage)
+                        }),
+                        map_res(
+                            captured_tag(
+                                syntax.captured_sublang_pairs,
+                                |p| &p.pair,
+                                line,
+                                position,
+                            ),
+                            |_| Ok::<_, NomError>(SyntaxType::SubLanguage),
+                        ),
                         map_res(tag_all(syntax.sublang_pairs, |p| p.0.left), |_| {
                             Ok::<_, NomError>(SyntaxType::SubLanguage)
                         }),
+                        // An empty comment, as `/**/`, opens no doc comment.
+                        map_res(
+                            |input| {
+                                let (rest, pair) =
+                                    tag_all(syntax.comment_pairs, |p| p.left)(input)?;
+                                tag(pair.right)(rest)
+                            },
+                            |_| Ok::<_, NomError>(SyntaxType::MultiComment),
+                        ),
                         map_res(tag_all(syntax.doc_comment_pairs, |p| p.left), |_| {
                             Ok::<_, NomError>(SyntaxType::DocMultiComment)
                         }),
+                        map_res(
+                            captured_tag(syntax.captured_doc_comment_pairs, |p| p, line, position),
+                            |_| Ok::<_, NomError>(SyntaxType::DocMultiComment),
+                        ),
                         map_res(tag_all(syntax.comment_pairs, |p| p.left), |_| {
                             Ok::<_, NomError>(SyntaxType::MultiComment)
                         }),
+                        map_res(
+                            captured_tag(syntax.captured_comment_pairs, |p| p, line, position),
+                            |_| Ok::<_, NomError>(SyntaxType::MultiComment),
+                        ),
+                        map_res(tag_all(syntax.datum_comment, |p| *p), |_| {
+                            Ok::<_, NomError>(SyntaxType::MultiComment)
+                        }),
+                        map_res(
+                            captured_tag(syntax.captured_quote_pairs, |p| p, line, position),
+                            |_| Ok::<_, NomError>(SyntaxType::LitString),
+                        ),
                         map_res(tag_all(syntax.literal_quote_pairs, |p| p.left), |_| {
                             Ok::<_, NomError>(SyntaxType::LitString)
                         }),
@@ -130,6 +415,31 @@ impl CoreParser {
                     ))),
                 )(line);
                 if let Ok((rest, (chars, syntax_type))) = parsed {
+                    let code = consumed(line, rest);
+                    let position = position.after(code);
+                    // A string may hold code of another language, named by a
+                    // comment on its line or the line before, or by the call
+                    // it is passed to.
+                    let injected = match syntax_type {
+                        SyntaxType::String | SyntaxType::LitString | SyntaxType::DocString => {
+                            let annotated = annotation
+                                .get()
+                                .filter(|(line, _)| line + 1 >= line_no)
+                                .map(|(_, text)| text);
+                            injected_language(syntax, code, annotated)
+                        }
+                        _ => None,
+                    };
+                    let syntax_type = if injected.is_some() {
+                        annotation.set(None);
+                        SyntaxType::SubLanguage
+                    } else {
+                        syntax_type
+                    };
+                    if !code.is_empty() {
+                        sink(Segment::new(SyntaxType::Code, line_no, code));
+                    }
+                    result.complexity += count_keywords(code, syntax.complexity_keywords);
                     if !prev_is_code
                         && !matches!(
                             syntax_type,
@@ -149,52 +459,189 @@ impl CoreParser {
                     }
                     match syntax_type {
                         SyntaxType::SubLanguage => {
-                            let (rest, (pair, lang_type)) =
-                                tag_all(syntax.sublang_pairs, |p| p.0.left)(rest)?;
-                            let (trailing_s, parsed) =
-                                split_sublang_part(pair, lang_type, rest, &mut lines)?;
-
-                            trailing_line = Some(trailing_s);
-                            result.join((*lang_type, parsed));
-
-                            if !is_newline {
-                                result.all -= 1;
+                            let start = rest;
+                            let template_tag = tag_all(template_tags, |p| p.left)(rest);
+                            let (rest, region, lang_type) = if let Some(lang_type) = injected {
+                                let (rest, region) =
+                                    Region::string(syntax, rest, position, code.len()).ok_or_else(
+                                        || CoreError::SyntaxError("Missing string.".to_string()),
+                                    )?;
+                                (rest, region, lang_type)
+                            } else if let (Some(template), Ok((_, pair))) =
+                                (syntax.template, template_tag)
+                            {
+                                // The tag keeps its delimiters.
+                                (rest, Region::template_tag(pair), template)
+                            } else if let Some((rest, sub, closer)) = match_captured(
+                                syntax.captured_sublang_pairs,
+                                |p| &p.pair,
+                                rest,
+                                position,
+                            ) {
+                                let column = code.len();
+                                (
+                                    rest,
+                                    Region::captured(&sub.pair, closer, column),
+                                    sub_language_of(sub, consumed(start, rest)),
+                                )
+                            } else {
+                                let (rest, (pair, lang_type)) =
+                                    tag_all(syntax.sublang_pairs, |p| p.0.left)(rest)?;
+                                (rest, Region::delimited(pair.right), *lang_type)
+                            };
+                            if region.opener.is_none() {
+                                sink(Segment::new(
+                                    SyntaxType::Code,
+                                    line_no,
+                                    consumed(start, rest),
+                                ));
                             }
+                            let opening_line = line_no;
+                            let mut part = split_sublang_part(
+                                &region,
+                                &lang_type,
+                                syntax.template,
+                                rest,
+                                &mut lines,
+                                &mut line_no,
+                                sink,
+                            )?;
 
-                            prev_is_code = false;
+                            if part.shares_opening_line {
+                                // The sub language counts the line, which it may
+                                // already have done for the region closed on it.
+                                if sublang_line == Some(opening_line) {
+                                    part.result.all -= 1;
+                                } else {
+                                    result.all -= 1;
+                                    if prev_is_code {
+                                        result.code -= 1;
+                                    }
+                                }
+                            } else if !prev_is_code {
+                                result.code += 1;
+                            }
+                            if part.host.blank > 0 {
+                                last_blank = line_no;
+                            }
+                            sublang_line = part.shares_last_line.then_some(line_no);
+                            result += part.host;
+                            if part.result.all > 0 {
+                                result.join((lang_type, part.result));
+                            }
+                            trailing_line = Some(part.trailing);
+                            prev_is_code = true;
                         }
                         SyntaxType::DocComment => {
+                            let (text, _) = tag_all(syntax.doc_comment, |p| *p)(rest)?;
                             trailing_line = None;
-                            result.comment.doc += 1;
+                            let code = if is_newline && code.trim().is_empty() {
+                                doc_code.line(syntax, text, line_no, &mut result, sink)?
+                            } else {
+                                None
+                            };
+                            if let Some(code) = code {
+                                // The line is counted with its block of code.
+                                sink(Segment::new(
+                                    SyntaxType::DocComment,
+                                    line_no,
+                                    consumed(rest, code),
+                                ));
+                                result.all -= 1;
+                            } else {
+                                sink(Segment::new(SyntaxType::DocComment, line_no, rest));
+                                result.comment.doc += 1;
+                            }
                         }
                         SyntaxType::SimpleComment => {
+                            if syntax.single_word_start
+                                && !code.is_empty()
+                                && !code
+                                    .ends_with(|c: char| c.is_whitespace() || ";|&()<>".contains(c))
+                            {
+                                // Not a comment, like `#` in `${#var}`.
+                                let (trailing, mark) =
+                                    tag_all(syntax.simple_comment, |p| *p)(rest)?;
+                                sink(Segment::new(SyntaxType::Code, line_no, mark));
+                                trailing_line = Some(trailing);
+                                prev_is_code = true;
+                                continue;
+                            }
+                            sink(Segment::new(SyntaxType::SimpleComment, line_no, rest));
                             trailing_line = None;
                             result.comment.normal += 1;
                         }
                         SyntaxType::DocMultiComment => {
-                            let (rest, pair) = tag_all(syntax.doc_comment_pairs, |p| p.left)(rest)?;
-                            let mut leading_map: BTreeMap<&str, usize> = BTreeMap::new();
-                            leading_map.insert(pair.left, 1);
-                            let mut trailing_map: BTreeMap<&str, usize> = BTreeMap::new();
-                            result.comment.doc += 1;
-                            if let Some(trailing) =
-                                multi_comment(&mut leading_map, &mut trailing_map, syntax, rest)
+                            let start = rest;
+                            let (rest, mut end) = if let Ok((rest, pair)) =
+                                tag_all(syntax.doc_comment_pairs, |p| p.left)(rest)
                             {
+                                (rest, CommentEnd::Pairs(vec![pair]))
+                            } else {
+                                let (rest, _, closer) = match_captured(
+                                    syntax.captured_doc_comment_pairs,
+                                    |p| p,
+                                    rest,
+                                    position,
+                                )
+                                .ok_or_else(|| {
+                                    CoreError::SyntaxError("Missing doc comment.".to_string())
+                                })?;
+                                (rest, CommentEnd::Captured(closer))
+                            };
+                            result.comment.doc += 1;
+                            if let Some(trailing) = end.find(syntax, rest) {
+                                sink(Segment::new(
+                                    SyntaxType::DocMultiComment,
+                                    line_no,
+                                    consumed(start, trailing),
+                                ));
                                 trailing_line = Some(trailing)
                             } else {
+                                sink(Segment::new(SyntaxType::DocMultiComment, line_no, start));
                                 'doc_comment: loop {
                                     if let Some(comment_line) = lines.next() {
                                         result.all += 1;
+                                        line_no += 1;
                                         result.comment.doc += 1;
-                                        if let Some(trailing) = multi_comment(
-                                            &mut leading_map,
-                                            &mut trailing_map,
-                                            syntax,
-                                            comment_line,
-                                        ) {
+                                        if let Some(trailing) = end.find(syntax, comment_line) {
+                                            sink(
+                                                Segment::new(
+                                                    SyntaxType::DocMultiComment,
+                                                    line_no,
+                                                    consumed(comment_line, trailing),
+                                                )
+                                                .continued(),
+                                            );
                                             trailing_line = Some(trailing);
                                             break 'doc_comment;
+                                        } else if let Some(code) = doc_code.line(
+                                            syntax,
+                                            comment_margin(comment_line),
+                                            line_no,
+                                            &mut result,
+                                            sink,
+                                        )? {
+                                            sink(
+                                                Segment::new(
+                                                    SyntaxType::DocMultiComment,
+                                                    line_no,
+                                                    consumed(comment_line, code),
+                                                )
+                                                .continued(),
+                                            );
+                                            result.all -= 1;
+                                            result.comment.doc -= 1;
+                                            continue 'doc_comment;
                                         } else {
+                                            sink(
+                                                Segment::new(
+                                                    SyntaxType::DocMultiComment,
+                                                    line_no,
+                                                    comment_line,
+                                                )
+                                                .continued(),
+                                            );
                                             continue 'doc_comment;
                                         }
                                     } else {
@@ -208,30 +655,55 @@ impl CoreParser {
                             prev_is_code = false;
                         }
                         SyntaxType::MultiComment => {
-                            let (rest, pair) = tag_all(syntax.comment_pairs, |p| p.left)(rest)?;
+                            let start = rest;
+                            let (rest, mut end) = if let Ok((rest, pair)) =
+                                tag_all(syntax.comment_pairs, |p| p.left)(rest)
+                            {
+                                (rest, CommentEnd::Pairs(vec![pair]))
+                            } else if let Some((rest, _, closer)) =
+                                match_captured(syntax.captured_comment_pairs, |p| p, rest, position)
+                            {
+                                (rest, CommentEnd::Captured(closer))
+                            } else {
+                                let (rest, _) = tag_all(syntax.datum_comment, |p| *p)(rest)?;
+                                (rest, CommentEnd::Datum(Datum::default()))
+                            };
 
-                            let mut leading_map: BTreeMap<&str, usize> = BTreeMap::new();
-                            leading_map.insert(pair.left, 1);
-                            let mut trailing_map: BTreeMap<&str, usize> = BTreeMap::new();
                             result.comment.normal += 1;
-                            if let Some(trailing) =
-                                multi_comment(&mut leading_map, &mut trailing_map, syntax, rest)
-                            {
+                            if let Some(trailing) = end.find(syntax, rest) {
+                                sink(Segment::new(
+                                    SyntaxType::MultiComment,
+                                    line_no,
+                                    consumed(start, trailing),
+                                ));
                                 trailing_line = Some(trailing)
                             } else {
+                                sink(Segment::new(SyntaxType::MultiComment, line_no, start));
                                 'normal_comment: loop {
                                     if let Some(comment_line) = lines.next() {
                                         result.all += 1;
+                                        line_no += 1;
                                         result.comment.normal += 1;
-                                        if let Some(trailing) = multi_comment(
-                                            &mut leading_map,
-                                            &mut trailing_map,
-                                            syntax,
-                                            comment_line,
-                                        ) {
+                                        if let Some(trailing) = end.find(syntax, comment_line) {
+                                            sink(
+                                                Segment::new(
+                                                    SyntaxType::MultiComment,
+                                                    line_no,
+                                                    consumed(comment_line, trailing),
+                                                )
+                                                .continued(),
+                                            );
                                             trailing_line = Some(trailing);
                                             break 'normal_comment;
                                         } else {
+                                            sink(
+                                                Segment::new(
+                                                    SyntaxType::MultiComment,
+                                                    line_no,
+                                                    comment_line,
+                                                )
+                                                .continued(),
+                                            );
                                             continue 'normal_comment;
                                         }
                                     } else {
@@ -245,25 +717,72 @@ impl CoreParser {
                             prev_is_code = false;
                         }
                         SyntaxType::DocString => {
+                            let start = rest;
                             let (rest, pair) = tag_all(syntax.doc_quote_pairs, |p| p.left)(rest)?;
 
                             if !prev_is_code {
                                 result.code += 1;
                             }
-                            if let Some(trailing) = normal_string(rest, pair) {
+                            if let Some(trailing) = string_end(rest, pair) {
+                                sink(Segment::new(
+                                    SyntaxType::DocString,
+                                    line_no,
+                                    consumed(start, trailing),
+                                ));
                                 result.comment.doc_quote += 1;
                                 trailing_line = Some(trailing);
+                            } else if !pair.multiline {
+                                sink(Segment::new(SyntaxType::DocString, line_no, start));
+                                result.comment.doc_quote += 1;
+                                trailing_line = None;
                             } else {
+                                sink(Segment::new(SyntaxType::DocString, line_no, start));
                                 result.comment.doc_quote += 1;
                                 'quote: loop {
                                     if let Some(comment_line) = lines.next() {
                                         result.all += 1;
+                                        line_no += 1;
                                         result.code += 1;
                                         result.comment.doc_quote += 1;
-                                        if let Some(trailing) = normal_string(comment_line, pair) {
+                                        if let Some(trailing) = string_end(comment_line, pair) {
+                                            sink(
+                                                Segment::new(
+                                                    SyntaxType::DocString,
+                                                    line_no,
+                                                    consumed(comment_line, trailing),
+                                                )
+                                                .continued(),
+                                            );
                                             trailing_line = Some(trailing);
                                             break 'quote;
+                                        } else if let Some(code) = doc_code.line(
+                                            syntax,
+                                            comment_line,
+                                            line_no,
+                                            &mut result,
+                                            sink,
+                                        )? {
+                                            sink(
+                                                Segment::new(
+                                                    SyntaxType::DocString,
+                                                    line_no,
+                                                    consumed(comment_line, code),
+                                                )
+                                                .continued(),
+                                            );
+                                            result.all -= 1;
+                                            result.code -= 1;
+                                            result.comment.doc_quote -= 1;
+                                            continue 'quote;
                                         } else {
+                                            sink(
+                                                Segment::new(
+                                                    SyntaxType::DocString,
+                                                    line_no,
+                                                    comment_line,
+                                                )
+                                                .continued(),
+                                            );
                                             continue 'quote;
                                         }
                                     } else {
@@ -277,25 +796,79 @@ impl CoreParser {
                             prev_is_code = true;
                         }
                         SyntaxType::LitString => {
-                            let (rest, pair) =
-                                tag_all(syntax.literal_quote_pairs, |p| p.left)(rest)?;
+                            let start = rest;
+                            let (rest, end, open_end) = if let Some((rest, pair, closer)) =
+                                match_captured(syntax.captured_quote_pairs, |p| p, rest, position)
+                            {
+                                if pair.next_line {
+                                    if !prev_is_code {
+                                        result.code += 1;
+                                    }
+                                    sink(Segment::new(
+                                        SyntaxType::LitString,
+                                        line_no,
+                                        consumed(start, rest),
+                                    ));
+                                    heredocs.push_back((closer, pair));
+                                    trailing_line = Some(rest);
+                                    prev_is_code = true;
+                                    continue;
+                                }
+                                (
+                                    rest,
+                                    LiteralEnd::Captured(closer, pair.escape),
+                                    pair.open_end,
+                                )
+                            } else {
+                                let (rest, pair) =
+                                    tag_all(syntax.literal_quote_pairs, |p| p.left)(rest)?;
+                                (rest, LiteralEnd::Pair(pair), false)
+                            };
 
                             if !prev_is_code {
                                 result.code += 1;
                             }
-                            if let Some(trailing) = litral_string(rest, pair) {
+                            if let Some(trailing) = end.find(rest) {
+                                sink(Segment::new(
+                                    SyntaxType::LitString,
+                                    line_no,
+                                    consumed(start, trailing),
+                                ));
                                 trailing_line = Some(trailing)
+                            } else if !end.multiline() {
+                                sink(Segment::new(SyntaxType::LitString, line_no, start));
+                                trailing_line = None;
                             } else {
+                                sink(Segment::new(SyntaxType::LitString, line_no, start));
                                 'lit_quote: loop {
                                     if let Some(comment_line) = lines.next() {
                                         result.all += 1;
+                                        line_no += 1;
                                         result.code += 1;
-                                        if let Some(trailing) = litral_string(comment_line, pair) {
+                                        if let Some(trailing) = end.find(comment_line) {
+                                            sink(
+                                                Segment::new(
+                                                    SyntaxType::LitString,
+                                                    line_no,
+                                                    consumed(comment_line, trailing),
+                                                )
+                                                .continued(),
+                                            );
                                             trailing_line = Some(trailing);
                                             break 'lit_quote;
                                         } else {
+                                            sink(
+                                                Segment::new(
+                                                    SyntaxType::LitString,
+                                                    line_no,
+                                                    comment_line,
+                                                )
+                                                .continued(),
+                                            );
                                             continue 'lit_quote;
                                         }
+                                    } else if open_end {
+                                        break 'lit_quote;
                                     } else {
                                         return Err(CoreError::SyntaxError(
                                             "No Literal Quote Ending found.".to_string(),
@@ -307,22 +880,49 @@ impl CoreParser {
                             prev_is_code = true;
                         }
                         SyntaxType::String => {
+                            let start = rest;
                             let (rest, pair) = tag_all(syntax.quote_pairs, |p| p.left)(rest)?;
 
                             if !prev_is_code {
                                 result.code += 1;
                             }
-                            if let Some(trailing) = normal_string(rest, pair) {
+                            if let Some(trailing) = string_end(rest, pair) {
+                                sink(Segment::new(
+                                    SyntaxType::String,
+                                    line_no,
+                                    consumed(start, trailing),
+                                ));
                                 trailing_line = Some(trailing)
+                            } else if !pair.multiline {
+                                sink(Segment::new(SyntaxType::String, line_no, start));
+                                trailing_line = None;
                             } else {
+                                sink(Segment::new(SyntaxType::String, line_no, start));
                                 'quote: loop {
                                     if let Some(comment_line) = lines.next() {
                                         result.all += 1;
+                                        line_no += 1;
                                         result.code += 1;
-                                        if let Some(trailing) = normal_string(comment_line, pair) {
+                                        if let Some(trailing) = string_end(comment_line, pair) {
+                                            sink(
+                                                Segment::new(
+                                                    SyntaxType::String,
+                                                    line_no,
+                                                    consumed(comment_line, trailing),
+                                                )
+                                                .continued(),
+                                            );
                                             trailing_line = Some(trailing);
                                             break 'quote;
                                         } else {
+                                            sink(
+                                                Segment::new(
+                                                    SyntaxType::String,
+                                                    line_no,
+                                                    comment_line,
+                                                )
+                                                .continued(),
+                                            );
                                             continue 'quote;
                                         }
                                     } else {
@@ -340,10 +940,14 @@ impl CoreParser {
                         | SyntaxType::Code
                         | SyntaxType::Eoi => unreachable!(),
                     }
-                } else if !prev_is_code {
-                    trailing_line = None;
-                    prev_is_code = false;
-                    result.code += 1;
+                } else {
+                    sink(Segment::new(SyntaxType::Code, line_no, line));
+                    result.complexity += count_keywords(line, syntax.complexity_keywords);
+                    if !prev_is_code {
+                        trailing_line = None;
+                        prev_is_code = false;
+                        result.code += 1;
+                    }
                 }
             }
 
@@ -352,3 +956,51 @@ impl CoreParser {
         Ok(result)
     }
 }
+
+/// Text of a line of a doc block comment without its `*` margin, as in
+/// Javadoc.
+fn comment_margin(line: &str) -> &str {
+    match line.trim_start().strip_prefix('*') {
+        Some(text) if !text.starts_with('/') => text,
+        _ => line,
+    }
+}
+
+/// The code of `line` after one of the `line_prefix` of `syntax`, which
+/// may be indented when `ignore_prefix_space` is set.
+fn strip_line_prefix<'a>(line: &'a str, syntax: &LanguageSyntax) -> Option<&'a str> {
+    let line = if syntax.ignore_prefix_space {
+        line.trim_start()
+    } else {
+        line
+    };
+    syntax
+        .line_prefix
+        .iter()
+        .find_map(|pf| line.strip_prefix(pf))
+}
+
+/// Whether `line` closes a heredoc, being made of `closer` alone, or of
+/// `closer` and code when it is `flexible`.
+fn closing_line(line: &str, closer: &str, flexible: bool) -> bool {
+    let line = line.trim();
+    line == closer
+        || flexible
+            && line
+                .strip_prefix(closer)
+                .is_some_and(|rest| !rest.starts_with(|c: char| c.is_alphanumeric() || c == '_'))
+}
+
+fn is_directive(line: &str, syntax: &LanguageSyntax) -> bool {
+    let line = line.trim_start();
+    syntax.preprocessor.iter().any(|p| line.starts_with(p))
+}
+
+/// Move the code blocks counted since a directive started to the
+/// preprocessor count.
+fn finish_directive(result: &mut ParseResult, directive: Option<usize>) {
+    if let Some(code) = directive {
+        result.preprocessor += result.code - code;
+        result.code = code;
+    }
+}
@@ -0,0 +1,120 @@
+use serde::Deserialize;
+
+use crate::{error::CoreError, language::LanguageType, parser::ParseResult};
+
+use super::CoreParser;
+
+/// The parts of an nbformat 4 notebook that are counted.
+#[derive(Debug, Deserialize)]
+struct Notebook {
+    #[serde(default)]
+    metadata: NotebookMetadata,
+    cells: Vec<Cell>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct NotebookMetadata {
+    kernelspec: Option<KernelSpec>,
+    language_info: Option<LanguageInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct KernelSpec {
+    language: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LanguageInfo {
+    name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Cell {
+    cell_type: String,
+    #[serde(default)]
+    source: Source,
+}
+
+/// Source of a cell, stored either whole or as a list of lines.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Source {
+    Lines(Vec<String>),
+    Text(String),
+}
+
+impl Default for Source {
+    fn default() -> Self {
+        Self::Text(String::new())
+    }
+}
+
+impl Source {
+    fn text(&self) -> String {
+        match self {
+            Self::Lines(lines) => lines.concat(),
+            Self::Text(text) => text.clone(),
+        }
+    }
+}
+
+impl NotebookMetadata {
+    /// Language of the kernel, from its spec, then from its language info.
+    /// Notebooks naming no language are Python, and notebooks naming none
+    /// that is known are counted as text.
+    fn language(&self) -> LanguageType {
+        let spec = self
+            .kernelspec
+            .as_ref()
+            .and_then(|spec| spec.language.as_deref());
+        let info = self
+            .language_info
+            .as_ref()
+            .and_then(|info| info.name.as_deref());
+        if spec.is_none() && info.is_none() {
+            return LanguageType::Python;
+        }
+        spec.into_iter()
+            .chain(info)
+            .find_map(LanguageType::from_name)
+            .unwrap_or(LanguageType::Text)
+    }
+}
+
+impl CoreParser {
+    /// Count a Jupyter notebook from its JSON `content`.
+    ///
+    /// Code cells are parsed with the language of the kernel and counted in
+    /// `sub_language`, while the lines of markdown cells are counted as doc
+    /// comments. Outputs and raw cells are left out.
+    pub fn parse_notebook(content: &str) -> Result<ParseResult, CoreError> {
+        let notebook: Notebook = serde_json::from_str(content)
+            .map_err(|e| CoreError::SyntaxError(format!("Invalid notebook: {}", e)))?;
+        let language = notebook.metadata.language();
+        let syntax = language.get_language_syntax();
+        let mut result = ParseResult::default();
+        for cell in &notebook.cells {
+            let source = cell.source.text();
+            match cell.cell_type.as_str() {
+                "code" => {
+                    let cell_result = Self::parse_lines(source.lines(), &syntax)?;
+                    if cell_result.all > 0 {
+                        result.join((language, cell_result));
+                    }
+                }
+                "markdown" => {
+                    for line in source.lines() {
+                        result.all += 1;
+                        if line.trim().is_empty() {
+                            result.blank += 1;
+                        } else {
+                            result.comment.doc += 1;
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(result)
+    }
+}
@@ -1,60 +1,54 @@
-use nom::{
-    branch::alt,
-    bytes::complete::tag,
-    character::complete::anychar,
-    combinator::{eof, map_res},
-    multi::many_till,
-};
+use crate::language::{Escape, SyntaxPair};
 
-use crate::{language::SyntaxPair, parser::NomError};
+/// End of a literal string, delimited either by a fixed pair or by a closing
+/// delimiter captured from the opening one.
+pub enum LiteralEnd<'p> {
+    Pair(&'p SyntaxPair),
+    Captured(String, Escape),
+}
 
-pub fn litral_string<'a>(line: &'a str, pair: &SyntaxPair) -> Option<&'a str> {
-    let mut line = line;
-    loop {
-        if line.is_empty() {
-            break None;
+impl LiteralEnd<'_> {
+    pub fn find<'a>(&self, line: &'a str) -> Option<&'a str> {
+        match self {
+            Self::Pair(pair) => string_end(line, pair),
+            Self::Captured(closer, escape) => closing_end(line, closer, *escape),
         }
-        if let Ok((rest, (_, (tagging, flag)))) = many_till(
-            anychar::<_, NomError>,
-            alt((
-                map_res(tag("\"\""), |c| Ok::<_, NomError>((c, false))),
-                map_res(tag(pair.right), |c| Ok::<_, NomError>((c, true))),
-                map_res(eof, |_| Ok::<_, NomError>(("", false))),
-            )),
-        )(line)
-        {
-            line = rest;
-            if tagging.is_empty() {
-                continue;
-            } else if flag {
-                break Some(line);
-            }
+    }
+
+    pub fn multiline(&self) -> bool {
+        match self {
+            Self::Pair(pair) => pair.multiline,
+            Self::Captured(..) => true,
         }
     }
 }
 
-pub fn normal_string<'a>(line: &'a str, pair: &SyntaxPair) -> Option<&'a str> {
-    let mut line = line;
+/// Find the closing delimiter of `pair` in `line`, following the escape rule
+/// of the pair, and return the rest of the line after it.
+pub fn string_end<'a>(line: &'a str, pair: &SyntaxPair) -> Option<&'a str> {
+    closing_end(line, pair.right, pair.escape)
+}
+
+/// Find `right` in `line`, following the `escape` rule, and return the rest
+/// of the line after it.
+pub fn closing_end<'a>(line: &'a str, right: &str, escape: Escape) -> Option<&'a str> {
+    let mut rest = line;
     loop {
-        if line.is_empty() {
-            break None;
+        if let Some(after) = rest.strip_prefix(right) {
+            match after.strip_prefix(right) {
+                Some(after) if escape == Escape::Doubled => rest = after,
+                _ => break Some(after),
+            }
+            continue;
         }
-        if let Ok((rest, (_, (tagging, flag)))) = many_till(
-            anychar::<_, NomError>,
-            alt((
-                map_res(tag("\\\\\""), |c| Ok::<_, NomError>((c, true))),
-                map_res(tag("\\\""), |c| Ok::<_, NomError>((c, false))),
-                map_res(tag(pair.right), |c| Ok::<_, NomError>((c, true))),
-                map_res(eof, |_| Ok::<_, NomError>(("", false))),
-            )),
-        )(line)
-        {
-            line = rest;
-            if tagging.is_empty() {
-                continue;
-            } else if flag {
-                break Some(line);
+        let mut chars = rest.chars();
+        match chars.next() {
+            None => break None,
+            Some('\\') if escape == Escape::Backslash => {
+                chars.next();
             }
+            Some(_) => {}
         }
+        rest = chars.as_str();
     }
 }
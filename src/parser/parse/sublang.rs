@@ -1,49 +1,381 @@
-use nom::bytes::complete::{tag, take_until};
+use std::iter::Peekable;
+
+use nom::bytes::complete::tag;
 
 use crate::{
     error::CoreError,
-    language::{LanguageType, SyntaxPair},
-    parser::{NomError, ParseResult},
+    language::{
+        CapturedPair, CapturedSubLanguage, Escape, LanguageSyntax, LanguageType, SyntaxPair,
+        SyntaxType,
+    },
+    parser::{
+        captured_group, match_captured, search_regex, tag_all, LinePosition, NomError, ParseResult,
+        Segment,
+    },
 };
 
-use super::CoreParser;
+use super::{string::closing_end, CoreParser};
+
+/// How an embedded region ends.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegionEnd {
+    /// The closing delimiter, anywhere in a line.
+    Delimiter(String),
+    /// A line made of the closing delimiter alone.
+    Line(String),
+    /// The first non-blank line indented no deeper than the column.
+    Dedent(usize),
+    /// The first line not ending with a backslash. Blank and comment lines
+    /// after the first one do not end the region either.
+    Continuation,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    pub end: RegionEnd,
+    /// The region starts on the line after the opening delimiter.
+    pub next_line: bool,
+    /// The region may end with the input.
+    pub open_end: bool,
+    /// Length of the opening delimiter when the region keeps its delimiters,
+    /// as template tags do. The leading text then starts with it.
+    pub opener: Option<usize>,
+    /// How the closing delimiter is kept from ending a string region.
+    pub escape: Escape,
+}
+
+impl Region {
+    pub fn delimited(closer: &str) -> Self {
+        Self {
+            end: RegionEnd::Delimiter(closer.to_string()),
+            next_line: false,
+            open_end: false,
+            opener: None,
+            escape: Escape::None,
+        }
+    }
+
+    /// The template tag opened by `pair`, delimiters included.
+    pub fn template_tag(pair: &SyntaxPair) -> Self {
+        Self {
+            opener: Some(pair.left.len()),
+            ..Self::delimited(pair.right)
+        }
+    }
+
+    /// The region opened by `pair` at `column`, closed by `closer`.
+    pub fn captured(pair: &CapturedPair, closer: String, column: usize) -> Self {
+        let end = if pair.indented {
+            RegionEnd::Dedent(column)
+        } else if pair.continued {
+            RegionEnd::Continuation
+        } else if pair.next_line {
+            RegionEnd::Line(closer)
+        } else {
+            RegionEnd::Delimiter(closer)
+        };
+        Self {
+            end,
+            next_line: pair.next_line,
+            open_end: pair.open_end || pair.continued,
+            opener: None,
+            escape: pair.escape,
+        }
+    }
 
-pub fn split_sublang_part<'a>(
-    pair: &SyntaxPair,
+    /// The string opened at the start of `input`, returning the rest of the
+    /// input after its opening quote.
+    pub fn string<'a>(
+        syntax: &LanguageSyntax,
+        input: &'a str,
+        position: LinePosition,
+        column: usize,
+    ) -> Option<(&'a str, Self)> {
+        if let Some((rest, pair, closer)) =
+            match_captured(syntax.captured_quote_pairs, |p| p, input, position)
+        {
+            return Some((rest, Self::captured(pair, closer, column)));
+        }
+        let (rest, pair) = tag_all(syntax.literal_quote_pairs, |p| p.left)(input)
+            .or_else(|_| tag_all(syntax.doc_quote_pairs, |p| p.left)(input))
+            .or_else(|_| tag_all(syntax.quote_pairs, |p| p.left)(input))
+            .ok()?;
+        Some((
+            rest,
+            Self {
+                escape: pair.escape,
+                ..Self::delimited(pair.right)
+            },
+        ))
+    }
+}
+
+/// Language of the region opened by `opening`, resolved through the aliases of
+/// [`LanguageType`] when the pair names it in a group.
+pub fn sub_language_of(sub: &CapturedSubLanguage, opening: &str) -> LanguageType {
+    sub.language_group
+        .and_then(|group| captured_group(&sub.pair, opening, group))
+        .filter(|name| !name.is_empty())
+        .and_then(LanguageType::from_name)
+        .unwrap_or(sub.language)
+}
+
+/// Language injected into a string opening after `code` on its line, by the
+/// first injection of `syntax` matching it and the `annotation` comment, if
+/// the language it names is known.
+pub fn injected_language(
+    syntax: &LanguageSyntax,
+    code: &str,
+    annotation: Option<&str>,
+) -> Option<LanguageType> {
+    syntax.injections.iter().find_map(|injection| {
+        let mut name = None;
+        for (pattern, text) in [
+            (injection.comment, annotation),
+            (injection.before, Some(code)),
+        ] {
+            let Some(pattern) = pattern else {
+                continue;
+            };
+            let caps = search_regex(pattern).captures(text?)?;
+            name = name.or_else(|| Some(caps.name(injection.language_group?)?.as_str()));
+        }
+        // A captured name that resolves to no language injects nothing.
+        match name.filter(|name| !name.is_empty()) {
+            Some(name) => LanguageType::from_name(name),
+            None => Some(injection.language),
+        }
+    })
+}
+
+/// An embedded region parsed with the syntax of its own language.
+pub struct SubLanguagePart<'a> {
+    /// The rest of the line after the closing delimiter.
+    pub trailing: &'a str,
+    pub result: ParseResult,
+    /// Embedded code follows the opening delimiter on its line, so that line
+    /// is counted by the sub language.
+    pub shares_opening_line: bool,
+    /// The sub language counts the last line read.
+    pub shares_last_line: bool,
+    /// Counts of the lines read past the opening one that belong to the host
+    /// language: a closing delimiter with nothing embedded before it, or the
+    /// blank lines around an indented region.
+    pub host: ParseResult,
+}
+
+/// Split `region` off the input and parse it as `sub_lang`, with the tags of
+/// the `template` layered over the host.
+///
+/// Lines carrying a delimiter belong to the host language, unless embedded
+/// code shares them.
+pub fn split_sublang_part<'a, I>(
+    region: &Region,
     sub_lang: &LanguageType,
+    template: Option<LanguageType>,
     leading: &'a str,
-    lines: &mut impl Iterator<Item = &'a str>,
-) -> Result<(&'a str, ParseResult), CoreError> {
-    let finish_tag = pair.right;
+    lines: &mut Peekable<I>,
+    line_no: &mut usize,
+    sink: &mut dyn FnMut(Segment<'a>),
+) -> Result<SubLanguagePart<'a>, CoreError>
+where
+    I: Iterator<Item = &'a str>,
+{
+    let is_blank = |content: &str| content.trim().is_empty();
+    let mut syntax = sub_lang.get_language_syntax();
+    if region.opener.is_none() {
+        syntax.template = template;
+    }
+    let is_comment = |content: &str| {
+        let content = content.trim_start();
+        syntax
+            .simple_comment
+            .iter()
+            .any(|cmt| content.starts_with(cmt))
+    };
+    let opening_line = *line_no;
+    let mut host = ParseResult::default();
     let mut sublang_content: Vec<&'a str> = Vec::new();
-    {
-        let line = leading;
-        if let Ok((rest, content)) = take_until::<_, _, NomError>(finish_tag)(line) {
+    // The closing line, and its text from the closing delimiter on.
+    let mut closing: Option<(usize, &'a str)> = None;
+    // Text of a line up to the closing delimiter, kept when the region keeps
+    // its delimiters, and the rest of the line from the delimiter on.
+    let until_closer = |text: &'a str, closer: &str, skip: usize| {
+        let after = closing_end(&text[skip..], closer, region.escape)?;
+        let rest = &text[text.len() - after.len() - closer.len()..];
+        let kept = if region.opener.is_some() {
+            closer.len()
+        } else {
+            0
+        };
+        Some((&text[..text.len() - rest.len() + kept], rest))
+    };
+
+    if region.next_line {
+        if !leading.is_empty() {
+            sink(Segment::new(SyntaxType::Code, opening_line, leading));
+        }
+    } else if let RegionEnd::Delimiter(closer) = &region.end {
+        if let Some((content, rest)) = until_closer(leading, closer, region.opener.unwrap_or(0)) {
+            closing = Some((opening_line, rest));
             sublang_content.push(content);
-            let syntax = sub_lang.get_language_syntax();
-            let result = CoreParser::parse_lines(sublang_content.clone().into_iter(), &syntax)?;
-            let (rest, _) = tag::<_, _, NomError>(finish_tag)(rest)?;
-            return Ok((rest, result));
         } else {
-            sublang_content.push(line);
-        }
-    }
-    loop {
-        if let Some(line) = lines.next() {
-            if let Ok((rest, content)) = take_until::<_, _, NomError>(finish_tag)(line) {
-                sublang_content.push(content);
-                let syntax = sub_lang.get_language_syntax();
-                let result = CoreParser::parse_lines(sublang_content.clone().into_iter(), &syntax)?;
-                let (rest, _) = tag::<_, _, NomError>(finish_tag)(rest)?;
-                break Ok((rest, result));
-            } else {
-                sublang_content.push(line);
-                continue;
+            sublang_content.push(leading);
+        }
+    } else {
+        sublang_content.push(leading);
+    }
+
+    while closing.is_none() {
+        if let RegionEnd::Dedent(column) = region.end {
+            match lines.peek() {
+                Some(line) if is_blank(line) || indentation(line) > column => {}
+                _ => break,
             }
-        } else {
-            break Err(CoreError::SyntaxError(
+        }
+        if let RegionEnd::Continuation = region.end {
+            let continues = match sublang_content.split_last() {
+                Some((last, before)) => {
+                    last.trim_end().ends_with('\\')
+                        || (!before.is_empty() && (is_blank(last) || is_comment(last)))
+                }
+                None => false,
+            };
+            if !continues {
+                break;
+            }
+        }
+        let Some(line) = lines.next() else {
+            if region.open_end {
+                break;
+            }
+            return Err(CoreError::SyntaxError(
                 "Ended sub language part.".to_string(),
             ));
+        };
+        *line_no += 1;
+        match &region.end {
+            RegionEnd::Delimiter(closer) => {
+                if let Some((content, rest)) = until_closer(line, closer, 0) {
+                    closing = Some((*line_no, rest));
+                    sublang_content.push(content);
+                    break;
+                }
+            }
+            RegionEnd::Line(closer) => {
+                if line.trim() == closer {
+                    closing = Some((*line_no, line));
+                    break;
+                }
+            }
+            RegionEnd::Dedent(_) | RegionEnd::Continuation => {}
+        }
+        sublang_content.push(line);
+    }
+
+    // Line of the first piece of content.
+    let first_line = opening_line + usize::from(region.next_line);
+    let mut host_blank = Vec::new();
+    let mut leading_blank = 0;
+    if let RegionEnd::Dedent(_) = region.end {
+        // Blank lines around an indented region are left to the host.
+        let keep = sublang_content
+            .iter()
+            .rposition(|content| !is_blank(content))
+            .map_or(0, |idx| idx + 1)
+            .max(usize::from(!region.next_line));
+        host_blank = sublang_content.split_off(keep);
+        if region.next_line {
+            leading_blank = sublang_content
+                .iter()
+                .take_while(|content| is_blank(content))
+                .count();
         }
     }
+    let shares_opening_line = !region.next_line
+        && sublang_content
+            .first()
+            .is_some_and(|content| !is_blank(content));
+    let start = usize::from(!region.next_line && !shares_opening_line) + leading_blank;
+    let mut end = sublang_content.len();
+    if let (Some((line, _)), RegionEnd::Delimiter(_)) = (closing, &region.end) {
+        if line != opening_line
+            && sublang_content
+                .last()
+                .is_some_and(|content| is_blank(content))
+        {
+            end -= 1;
+        }
+    }
+    let end = end.max(start);
+    let blank = |line: usize, content: &'a str| Segment {
+        sub_language: Some(*sub_lang),
+        ..Segment::new(SyntaxType::Blank, line, content)
+    };
+
+    if start > leading_blank && !sublang_content[0].is_empty() {
+        sink(blank(first_line, sublang_content[0]));
+    }
+    for (idx, content) in sublang_content[..leading_blank].iter().enumerate() {
+        sink(Segment::new(SyntaxType::Blank, first_line + idx, content));
+        host.all += 1;
+        host.blank += 1;
+    }
+    let offset = first_line + start - 1;
+    let result = if start < end {
+        CoreParser::walk_lines(
+            sublang_content[start..end].iter().copied(),
+            &syntax,
+            &mut |seg| {
+                sink(Segment {
+                    line: seg.line + offset,
+                    sub_language: seg.sub_language.or(Some(*sub_lang)),
+                    ..seg
+                })
+            },
+        )?
+    } else {
+        ParseResult::default()
+    };
+    let shares_last_line = start < end && first_line + end - 1 == *line_no;
+    if end < sublang_content.len() && !sublang_content[end].is_empty() {
+        sink(blank(first_line + end, sublang_content[end]));
+    }
+
+    let mut trailing = "";
+    if let Some((line, rest)) = closing {
+        let finish = match &region.end {
+            RegionEnd::Delimiter(closer) => {
+                let (rest, finish) = tag::<_, _, NomError>(closer.as_str())(rest)?;
+                trailing = rest;
+                finish
+            }
+            _ => rest,
+        };
+        if region.opener.is_none() {
+            sink(Segment::new(SyntaxType::Code, line, finish));
+        }
+        if line != opening_line && !shares_last_line {
+            host.all += 1;
+            host.code += 1;
+        }
+    }
+    let blank_line = first_line + sublang_content.len();
+    for (idx, content) in host_blank.into_iter().enumerate() {
+        sink(Segment::new(SyntaxType::Blank, blank_line + idx, content));
+        host.all += 1;
+        host.blank += 1;
+    }
+
+    Ok(SubLanguagePart {
+        trailing,
+        result,
+        shares_opening_line,
+        shares_last_line,
+        host,
+    })
+}
+
+fn indentation(line: &str) -> usize {
+    line.len() - line.trim_start().len()
 }
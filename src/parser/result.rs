@@ -1,4 +1,7 @@
-use std::{collections::BTreeMap, ops::{Add, AddAssign}};
+use std::{
+    collections::BTreeMap,
+    ops::{Add, AddAssign},
+};
 
 use crate::language::LanguageType;
 
@@ -7,16 +10,23 @@ pub struct ParseResult {
     pub code: usize,
     pub blank: usize,
     pub all: usize,
+    /// Number of branching keywords found in code regions, a rough estimate of
+    /// the cyclomatic complexity.
+    pub complexity: usize,
+    /// Preprocessor directive blocks, counted separately from code.
+    pub preprocessor: usize,
     pub comment: CommentResult,
     pub sub_language: BTreeMap<LanguageType, ParseResult>,
 }
 
 impl ParseResult {
     pub fn join(&mut self, other: (LanguageType, Self)) {
-        self.sub_language.entry(other.0).or_insert(other.1.clone());
+        *self.sub_language.entry(other.0).or_default() += other.1.clone();
         self.code += other.1.code;
         self.blank += other.1.blank;
         self.all += other.1.all;
+        self.complexity += other.1.complexity;
+        self.preprocessor += other.1.preprocessor;
         self.comment += other.1.comment;
     }
 }
@@ -28,6 +38,8 @@ impl Add for ParseResult {
             code: self.code + rhs.code,
             blank: self.blank + rhs.blank,
             all: self.all + rhs.all,
+            complexity: self.complexity + rhs.complexity,
+            preprocessor: self.preprocessor + rhs.preprocessor,
             comment: self.comment + rhs.comment,
             sub_language: {
                 let mut map: BTreeMap<LanguageType, ParseResult> = BTreeMap::new();
@@ -35,10 +47,10 @@ impl Add for ParseResult {
                     map.entry(*k).or_insert(v.clone());
                 }
                 for (k, v) in rhs.sub_language.iter() {
-                    *map.entry(*k).or_insert(ParseResult::default()) += v.clone();
+                    *map.entry(*k).or_default() += v.clone();
                 }
                 map
-            }
+            },
         }
     }
 }
@@ -48,9 +60,11 @@ impl AddAssign for ParseResult {
         self.code += rhs.code;
         self.blank += rhs.blank;
         self.all += rhs.all;
+        self.complexity += rhs.complexity;
+        self.preprocessor += rhs.preprocessor;
         self.comment += rhs.comment;
         for (k, v) in rhs.sub_language.iter() {
-            *self.sub_language.entry(*k).or_insert(ParseResult::default()) += v.clone()
+            *self.sub_language.entry(*k).or_default() += v.clone()
         }
     }
 }
@@ -68,7 +82,7 @@ impl Add for CommentResult {
         Self {
             doc: self.doc + rhs.doc,
             normal: self.normal + rhs.normal,
-            doc_quote: self.doc_quote + rhs.doc_quote, 
+            doc_quote: self.doc_quote + rhs.doc_quote,
         }
     }
 }
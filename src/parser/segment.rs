@@ -0,0 +1,66 @@
+use crate::language::{LanguageType, SyntaxType};
+
+/// A piece of a source line, classified by the parser.
+///
+/// Concatenating the segments of a line (in order) yields the line itself,
+/// without its `line_prefix` or the text outside its `code_columns`. Regions spanning several lines, such as
+/// multi-line comments and strings, produce one segment per line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Segment<'a> {
+    pub syntax_type: SyntaxType,
+    /// 1-based line number.
+    pub line: usize,
+    pub text: &'a str,
+    /// Whether the segment continues a region opened on a previous line.
+    pub continued: bool,
+    /// The embedded language the segment belongs to, `None` for the host
+    /// language.
+    pub sub_language: Option<LanguageType>,
+    /// Whether the segment is code of a doc comment or doc string, as the
+    /// examples of Rust docs, counted when the parser is asked to.
+    pub doc_code: bool,
+}
+
+impl<'a> Segment<'a> {
+    pub(crate) fn new(syntax_type: SyntaxType, line: usize, text: &'a str) -> Self {
+        Self {
+            syntax_type,
+            line,
+            text,
+            continued: false,
+            sub_language: None,
+            doc_code: false,
+        }
+    }
+
+    pub(crate) fn continued(self) -> Self {
+        Self {
+            continued: true,
+            ..self
+        }
+    }
+
+    pub fn is_comment(&self) -> bool {
+        matches!(
+            self.syntax_type,
+            SyntaxType::SimpleComment
+                | SyntaxType::DocComment
+                | SyntaxType::MultiComment
+                | SyntaxType::DocMultiComment
+        )
+    }
+
+    pub fn is_doc(&self) -> bool {
+        matches!(
+            self.syntax_type,
+            SyntaxType::DocComment | SyntaxType::DocMultiComment | SyntaxType::DocString
+        )
+    }
+
+    pub fn is_string(&self) -> bool {
+        matches!(
+            self.syntax_type,
+            SyntaxType::String | SyntaxType::LitString | SyntaxType::DocString
+        )
+    }
+}
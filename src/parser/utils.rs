@@ -1,5 +1,13 @@
+use std::{
+    collections::HashMap,
+    sync::{Mutex, OnceLock},
+};
+
 use nom::bytes::complete::tag;
 use nom::IResult;
+use regex::Regex;
+
+use crate::language::CapturedPair;
 
 pub fn tag_all<'a, T>(
     tags: &'a [T],
@@ -22,3 +30,252 @@ where
         )))
     }
 }
+
+/// Count the occurrences of `keywords` in a piece of code.
+///
+/// Keywords made of identifier characters (e.g. `if`) only match as whole
+/// words, while operators (e.g. `&&`, `?`) match anywhere.
+pub fn count_keywords(code: &str, keywords: &[&str]) -> usize {
+    keywords
+        .iter()
+        .filter(|kw| !kw.is_empty())
+        .map(|kw| match_keyword(code, kw).count())
+        .sum()
+}
+
+/// Byte offsets of `keyword` in `text`, with the same matching rules as
+/// [`count_keywords`].
+pub fn match_keyword<'a>(text: &'a str, keyword: &'a str) -> impl Iterator<Item = usize> + 'a {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    let word = keyword.chars().all(is_ident);
+    text.match_indices(keyword)
+        .map(|(idx, _)| idx)
+        .filter(move |idx| {
+            !word
+                || (!text[..*idx].ends_with(is_ident)
+                    && !text[idx + keyword.len()..].starts_with(is_ident))
+        })
+}
+
+/// The part of `input` consumed before reaching `rest`, which must be a suffix
+/// of `input`.
+pub fn consumed<'a>(input: &'a str, rest: &str) -> &'a str {
+    &input[..input.len() - rest.len()]
+}
+
+/// Byte offset of the 1-based `column` of `line`, counted in characters, or
+/// the length of the line past its end.
+fn column_offset(line: &str, column: usize) -> usize {
+    line.char_indices()
+        .nth(column.saturating_sub(1))
+        .map_or(line.len(), |(idx, _)| idx)
+}
+
+/// The part of `line` in the 1-based, inclusive range of columns.
+pub fn column_range(line: &str, (start, end): (usize, usize)) -> &str {
+    let start = column_offset(line, start);
+    let end = column_offset(line, end + 1).max(start);
+    &line[start..end]
+}
+
+/// Whether one of `markers` sits at its column of `line`.
+pub fn at_column(line: &str, markers: &[(usize, &str)]) -> bool {
+    markers.iter().any(|(column, marker)| {
+        let idx = column_offset(line, *column);
+        idx < line.len() && line[idx..].starts_with(marker)
+    })
+}
+
+/// A pattern compiled once and shared for the rest of the program.
+struct CachedRegex {
+    regex: Regex,
+    /// The bytes a match may start with, or `None` if it may start with any
+    /// byte or be empty.
+    first_bytes: Option<[bool; 256]>,
+}
+
+impl CachedRegex {
+    fn new(source: &str) -> Self {
+        let regex = Regex::new(source).expect("Invalid delimiter pattern");
+        Self {
+            first_bytes: first_bytes(source),
+            regex,
+        }
+    }
+
+    /// Whether a match may start at the beginning of `input`.
+    fn may_start(&self, input: &str) -> bool {
+        may_start(&self.first_bytes, input)
+    }
+}
+
+fn may_start(first_bytes: &Option<[bool; 256]>, input: &str) -> bool {
+    match (first_bytes, input.as_bytes().first()) {
+        (Some(bytes), Some(&first)) => bytes[first as usize],
+        (Some(_), None) => false,
+        (None, _) => true,
+    }
+}
+
+/// The bytes the matches of `source` start with, found from its literal
+/// prefixes.
+fn first_bytes(source: &str) -> Option<[bool; 256]> {
+    let hir = regex_syntax::Parser::new().parse(source).ok()?;
+    let prefixes = regex_syntax::hir::literal::Extractor::new().extract(&hir);
+    let mut bytes = [false; 256];
+    for literal in prefixes.literals()? {
+        bytes[*literal.as_bytes().first()? as usize] = true;
+    }
+    Some(bytes)
+}
+
+type RegexCache = OnceLock<Mutex<HashMap<&'static str, &'static CachedRegex>>>;
+
+/// Compile `pattern` as `source` once per pattern.
+fn cached_regex(
+    cache: &RegexCache,
+    pattern: &'static str,
+    source: impl FnOnce() -> String,
+) -> &'static CachedRegex {
+    let mut cache = cache
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    cache
+        .entry(pattern)
+        .or_insert_with(|| Box::leak(Box::new(CachedRegex::new(&source()))))
+}
+
+fn anchored(pattern: &'static str) -> &'static CachedRegex {
+    static CACHE: RegexCache = OnceLock::new();
+    cached_regex(&CACHE, pattern, || format!("^(?:{})", pattern))
+}
+
+/// Compile `pattern` anchored at the start of the input, once per pattern.
+pub fn anchored_regex(pattern: &'static str) -> &'static Regex {
+    &anchored(pattern).regex
+}
+
+/// Compile `pattern` to match the whole input, once per pattern.
+pub fn full_regex(pattern: &'static str) -> &'static Regex {
+    static CACHE: RegexCache = OnceLock::new();
+    &cached_regex(&CACHE, pattern, || format!("^(?:{})$", pattern)).regex
+}
+
+/// Compile `pattern` to be searched anywhere in the input, once per pattern.
+pub fn search_regex(pattern: &'static str) -> &'static Regex {
+    static CACHE: RegexCache = OnceLock::new();
+    &cached_regex(&CACHE, pattern, || pattern.to_string()).regex
+}
+
+/// Where a piece of input starts within its line, for the delimiters that
+/// only open at the beginning of a line, after its indentation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LinePosition {
+    Inside,
+    /// The beginning of a line, after its indentation.
+    Indented,
+    Start,
+    /// The beginning of a line following a blank line, or of the input.
+    AfterBlank,
+}
+
+impl LinePosition {
+    /// The position of the input following `before` on its line, where `self`
+    /// is the position of `before`.
+    pub fn after(self, before: &str) -> Self {
+        if before.is_empty() {
+            self
+        } else if self != Self::Inside && before.trim().is_empty() {
+            Self::Indented
+        } else {
+            Self::Inside
+        }
+    }
+
+    fn allows(self, pair: &CapturedPair) -> bool {
+        match self {
+            Self::Inside => !pair.line_start && !pair.after_blank && !pair.unindented,
+            Self::Indented => !pair.after_blank && !pair.unindented,
+            Self::Start => !pair.after_blank,
+            Self::AfterBlank => true,
+        }
+    }
+}
+
+/// Match the opening delimiter of one of `pairs` at the start of `input`,
+/// returning the rest of the input and the expanded closing delimiter.
+pub fn match_captured<'a, 'p, T>(
+    pairs: &'p [T],
+    get_pair: impl Fn(&T) -> &CapturedPair,
+    input: &'a str,
+    position: LinePosition,
+) -> Option<(&'a str, &'p T, String)> {
+    pairs.iter().find_map(|item| {
+        let pair = get_pair(item);
+        if !position.allows(pair) {
+            return None;
+        }
+        let regex = anchored(pair.left);
+        if !regex.may_start(input) {
+            return None;
+        }
+        let caps = regex.regex.captures(input)?;
+        let opening = caps.get(0)?;
+        if opening.is_empty() {
+            return None;
+        }
+        let mut closer = String::new();
+        caps.expand(pair.right, &mut closer);
+        Some((&input[opening.end()..], item, closer))
+    })
+}
+
+/// Text of the group `name` in the opening delimiter of `pair`.
+pub fn captured_group<'a>(pair: &CapturedPair, opening: &'a str, name: &str) -> Option<&'a str> {
+    let caps = anchored_regex(pair.left).captures(opening)?;
+    caps.name(name).map(|m| m.as_str())
+}
+
+/// Parser for the opening delimiters of `pairs`, where `line` is the line the
+/// input is a suffix of and `position` tells where that line starts.
+pub fn captured_tag<'p, 'l, T, F>(
+    pairs: &'p [T],
+    get_pair: F,
+    line: &'l str,
+    position: LinePosition,
+) -> impl FnMut(&str) -> IResult<&str, &'p T> + 'l
+where
+    F: Fn(&T) -> &CapturedPair + 'l,
+    'p: 'l,
+{
+    // The bytes any opening delimiter may start with, so that the patterns
+    // are only tried where one of them may match.
+    let mut first_bytes = Some([false; 256]);
+    for item in pairs {
+        let pair_bytes = anchored(get_pair(item).left).first_bytes;
+        first_bytes = first_bytes.zip(pair_bytes).map(|(mut bytes, pair_bytes)| {
+            bytes
+                .iter_mut()
+                .zip(pair_bytes)
+                .for_each(|(byte, pair_byte)| *byte |= pair_byte);
+            bytes
+        });
+    }
+    move |input| {
+        if !may_start(&first_bytes, input) {
+            return Err(nom::Err::Error(nom::error::Error::new(
+                input,
+                nom::error::ErrorKind::Tag,
+            )));
+        }
+        let position = position.after(consumed(line, input));
+        match match_captured(pairs, &get_pair, input, position) {
+            Some((rest, item, _)) => Ok((rest, item)),
+            None => Err(nom::Err::Error(nom::error::Error::new(
+                input,
+                nom::error::ErrorKind::Tag,
+            ))),
+        }
+    }
+}
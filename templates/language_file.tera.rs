@@ -1,12 +1,13 @@
 LanguageFile {
     extension: {% if extension is defined %} Some(FileItem {
-        regex: &[{%- for reg in extension.regex %} regex::Regex::new("{{reg}}").unwrap(), {% endfor -%}],
+        regex: &[{%- for reg in extension.regex %} r#"{{reg}}"#, {% endfor -%}],
         case_insensitive: &[{%- for ci in extension.case_insensitive %} "{{ci}}", {% endfor -%}],
         plain: &[{%- for ci in extension.plain %} "{{ci}}", {% endfor -%}],
     }) {% else %} None {% endif %},
     file_name: {% if file_name is defined %} Some(FileItem {
-        regex: &[{%- for reg in file_name.regex %} regex::Regex::new("{{reg}}").unwrap(), {% endfor -%}],
+        regex: &[{%- for reg in file_name.regex %} r#"{{reg}}"#, {% endfor -%}],
         case_insensitive: &[{%- for ci in file_name.case_insensitive %} "{{ci}}", {% endfor -%}],
         plain: &[{%- for ci in file_name.plain %} "{{ci}}", {% endfor -%}],
     }) {% else %} None {% endif %},
+    path: &[{%- for glob in path %} r#"{{glob}}"#, {% endfor -%}],
 }
\ No newline at end of file
@@ -1,12 +1,19 @@
+{% import "macros" as macros %}
 LanguageSyntax {
-    line_prefix: {% if line_prefix %} Some("{{line_prefix}}") {% else %} None {% endif %},
+    line_prefix: &[{%- for pf in line_prefix -%}"{{pf}}", {% endfor -%}],
+    missing_prefix: MissingPrefix::{{ missing_prefix }},
     ignore_prefix_space: {{ ignore_prefix_space }},
+    literate: {{ literate }},
+    code_columns: {% if columns %} Some(({{columns[0]}}, {{columns[1]}})) {% else %} None {% endif %},
     block_pairs: &[
         {% for b in block -%}
         SyntaxPair {
             name: "block{{- loop.index}}",
             left: "{{b[0]}}",
             right: "{{b[1]}}",
+            nested: false,
+            escape: Escape::None,
+            multiline: true,
         },
         {% endfor %}
     ],
@@ -14,8 +21,11 @@ LanguageSyntax {
         {% for cop in comment.multi -%}
         SyntaxPair {
             name: "cop{{- loop.index}}",
-            left: "{{cop[0]}}",
-            right: "{{cop[1]}}",
+            left: "{{cop.left}}",
+            right: "{{cop.right}}",
+            nested: {{ cop.nested }},
+            escape: Escape::None,
+            multiline: true,
         },
         {% endfor %}
     ],
@@ -23,28 +33,73 @@ LanguageSyntax {
         {% for quotep in quote.normal -%}
         SyntaxPair {
             name: "quotep{{- loop.index}}",
-            left: "{{quotep[0]}}",
-            right: "{{quotep[1]}}",
+            left: "{{quotep.left}}",
+            right: "{{quotep.right}}",
+            nested: false,
+            escape: Escape::{% if quotep.escape %}{{ quotep.escape }}{% else %}Backslash{% endif %},
+            multiline: {{ quotep.multiline }},
         },
         {% endfor %}
     ],
+    captured_quote_pairs: &[
+        {% for cqp in quote.captured -%}
+        {{ macros::captured_pair(name="cqp" ~ loop.index, pair=cqp) }},
+        {% endfor %}
+    ],
+    captured_comment_pairs: &[
+        {% for ccp in comment.captured -%}
+        {{ macros::captured_pair(name="ccp" ~ loop.index, pair=ccp) }},
+        {% endfor %}
+    ],
+    captured_doc_comment_pairs: &[
+        {% for cdp in comment.doc_captured -%}
+        {{ macros::captured_pair(name="cdp" ~ loop.index, pair=cdp) }},
+        {% endfor %}
+    ],
+    literate_code_pairs: &[
+        {% for lcp in literate_code -%}
+        {{ macros::captured_pair(name="lcp" ~ loop.index, pair=lcp) }},
+        {% endfor %}
+    ],
     literal_quote_pairs: &[
         {% for litqp in quote.literal -%}
         SyntaxPair {
             name: "litqp{{- loop.index}}",
-            left: "{{litqp[0]}}",
-            right: "{{litqp[1]}}",
+            left: "{{litqp.left}}",
+            right: "{{litqp.right}}",
+            nested: false,
+            escape: Escape::{% if litqp.escape %}{{ litqp.escape }}{% else %}None{% endif %},
+            multiline: {{ litqp.multiline }},
+        },
+        {% endfor %}
+    ],
+    injections: &[
+        {% for inj in injection -%}
+        Injection {
+            comment: {% if inj.comment %} Some(r#"{{inj.comment}}"#) {% else %} None {% endif %},
+            before: {% if inj.before %} Some(r#"{{inj.before}}"#) {% else %} None {% endif %},
+            language: LanguageType::{{inj.language}},
+            language_group: {% if inj.language_group %} Some("{{inj.language_group}}") {% else %} None {% endif %},
         },
         {% endfor %}
     ],
     simple_comment: &[{%- for cmt in comment.single -%}"{{cmt}}", {% endfor -%}],
     doc_comment: &[{%- for cmt in comment.doc -%}"{{cmt}}", {% endfor -%}],
+    complexity_keywords: &[{%- for kw in complexity -%}"{{kw}}", {% endfor -%}],
+    column_comment: &[{%- for cc in comment.column -%}({{cc[0]}}, "{{cc[1]}}"), {% endfor -%}],
+    datum_comment: &[{%- for dc in comment.datum -%}"{{dc}}", {% endfor -%}],
+    single_word_start: {{ comment.word_start }},
+    end_comment: &[{%- for ec in comment.end -%}"{{ec}}", {% endfor -%}],
+    preprocessor: &[{%- for pp in preprocessor -%}"{{pp}}", {% endfor -%}],
     doc_quote_pairs: &[
         {% for dqp in quote.doc -%}
         SyntaxPair {
             name: "dqp{{- loop.index}}",
-            left: "{{dqp[0]}}",
-            right: "{{dqp[1]}}",
+            left: "{{dqp.left}}",
+            right: "{{dqp.right}}",
+            nested: false,
+            escape: Escape::{% if dqp.escape %}{{ dqp.escape }}{% else %}Backslash{% endif %},
+            multiline: {{ dqp.multiline }},
         },
         {% endfor %}
     ],
@@ -54,15 +109,52 @@ LanguageSyntax {
             name: "sbp{{- loop.index}}",
             left: "{{sbp[0]}}",
             right: "{{sbp[1]}}",
+            nested: false,
+            escape: Escape::None,
+            multiline: true,
         }, LanguageType::{{sbp[2]}}),
         {% endfor %}
     ],
+    captured_sublang_pairs: &[
+        {% for csp in captured_sub_language -%}
+        CapturedSubLanguage {
+            pair: {{ macros::captured_pair(name="csp" ~ loop.index, pair=csp) }},
+            language: LanguageType::{{csp.language}},
+            language_group: {% if csp.language_group %} Some("{{csp.language_group}}") {% else %} None {% endif %},
+        },
+        {% endfor %}
+    ],
+    doc_code_pairs: &[
+        {% for dcd in doc_code -%}
+        CapturedSubLanguage {
+            pair: {{ macros::captured_pair(name="dcd" ~ loop.index, pair=dcd) }},
+            language: LanguageType::{{dcd.language}},
+            language_group: {% if dcd.language_group %} Some("{{dcd.language_group}}") {% else %} None {% endif %},
+        },
+        {% endfor %}
+    ],
+    template: None,
+    template_tags: &[
+        {% for tt in template -%}
+        SyntaxPair {
+            name: "tt{{- loop.index}}",
+            left: "{{tt[0]}}",
+            right: "{{tt[1]}}",
+            nested: false,
+            escape: Escape::None,
+            multiline: true,
+        },
+        {% endfor %}
+    ],
     doc_comment_pairs: &[
         {% for dcp in comment.doc_multi -%}
         SyntaxPair {
             name: "dcp{{- loop.index}}",
-            left: "{{dcp[0]}}",
-            right: "{{dcp[1]}}",
+            left: "{{dcp.left}}",
+            right: "{{dcp.right}}",
+            nested: {{ dcp.nested }},
+            escape: Escape::None,
+            multiline: true,
         },
         {% endfor %}
     ]
@@ -0,0 +1,16 @@
+{% macro captured_pair(name, pair) -%}
+CapturedPair {
+            name: "{{ name }}",
+            left: r#"{{pair.left}}"#,
+            right: r#"{{pair.right}}"#,
+            next_line: {{ pair.next_line }},
+            open_end: {{ pair.open_end }},
+            line_start: {{ pair.line_start }},
+            after_blank: {{ pair.after_blank }},
+            unindented: {{ pair.unindented }},
+            indented: {{ pair.indented }},
+            continued: {{ pair.continued }},
+            flexible: {{ pair.flexible }},
+            escape: Escape::{% if pair.escape %}{{ pair.escape }}{% else %}None{% endif %},
+        }
+{%- endmacro captured_pair %}
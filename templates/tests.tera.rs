@@ -15,7 +15,7 @@ mod tests {
         assert_eq!(result, {{language.predict}});
 
         let file = LanguageType::{{language.ident}}.get_language_file();
-        let to_parse = &[{%- for s in language.detect %}Path::new("{{s}}"), {% endfor -%}];
+        let to_parse: &[&Path] = &[{%- for s in language.detect %}Path::new("{{s}}"), {% endfor -%}];
         for p in to_parse.iter() {
             let res = file.is_match_file(p);
             assert!(res);
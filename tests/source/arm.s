@@ -0,0 +1,7 @@
+@ ARM assembly example
+    .global _start
+_start:
+    mov r0, #1      @ stdout
+    ldr r1, =msg    // also a comment
+    /* block */
+    swi 0
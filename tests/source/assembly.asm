@@ -0,0 +1,10 @@
+; NASM example
+section .data
+    msg db "Hello; not a comment", 10
+    len equ $ - msg
+* column-one comment
+section .text
+global _start
+_start:
+    mov rax, 1 ; write
+    syscall
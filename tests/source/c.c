@@ -0,0 +1,23 @@
+/* SPDX-License-Identifier: MIT */
+#include <stdio.h>
+#define MAX(a, b) \
+    ((a) > (b) ? (a) : (b))
+
+/**
+ * Doxygen comment.
+ */
+static const char *s = "/* not a comment */";
+/* C comments do not nest: /* */ static int after_comment = 0;
+
+// Entry point.
+int main(void) {
+    int x = MAX(1, 2), y = 0; /* inline */
+#ifdef DEBUG
+    printf("%d\n", x);
+#endif
+    if (x > 1 && y == 0) {
+        return 1;
+    }
+
+    return 0;
+}
@@ -0,0 +1,13 @@
+;; Greeting utilities.
+(ns demo.core)
+
+(defn greet
+  "Returns a greeting; not a comment."
+  [name]
+  #_(println "debug"
+             name)
+  (if (and name (not= name \"))
+    (str "Hello, " name)
+    "nobody"))
+
+#_ignored-symbol (greet "world")
@@ -0,0 +1,11 @@
+;;;; Example system.
+(defpackage :demo (:use :cl))
+(in-package :demo)
+
+#| Block comment
+   #| nested |# still inside
+|#
+(defun greet (name)
+  "Docstring with #| no comment |#."
+  (when (and name (char/= (char name 0) #\"))
+    (format t "Hello, ~a~%" name)))
@@ -0,0 +1,21 @@
+#include <string>
+#pragma once
+
+/// Doc comment.
+namespace demo {
+// A raw string containing quotes and comment markers.
+const std::string raw = R"(say "hi" /* not a comment */)";
+const std::string multi = R"(
+line one
+// line two
+)";
+const std::string delim = u8R"sql(say ")" here)sql"; const char quote = '"';
+
+int run(int n) {
+    for (int i = 0; i < n; ++i) {
+        if (i % 2 == 0 || i == 3) { continue; }
+    }
+    try { throw 1; } catch (...) {}
+    return n; /* done */
+}
+} // namespace demo
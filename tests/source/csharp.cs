@@ -0,0 +1,19 @@
+#region Usings
+using System;
+#endregion
+
+/// <summary>Entry point.</summary>
+class Program
+{
+    static void Main()
+    {
+        var path = @"C:\temp\""quoted"".txt";
+        var name = "World";
+        var msg = @$"Hello {name}
+// still a string";
+        var raw = """
+            /* raw */ "text"
+            """;
+        if (path != null && msg != null) { Console.WriteLine(raw); }
+    }
+}
@@ -0,0 +1,12 @@
+/// Dart doc comment.
+void main() {
+  var single = 'single "quoted"';
+  var raw = r'C:\path\';
+  var multi = '''
+  // not a comment
+  ''';
+  /* block comment */
+  if (single.isNotEmpty && raw.isNotEmpty) {
+    print(multi ?? "none");
+  }
+}
@@ -0,0 +1,15 @@
+defmodule Greeter do
+  @moduledoc """
+  Greets people. # not a comment
+  """
+
+  @doc "Says hello."
+  def hello(name) when is_binary(name) do
+    # A comment
+    if name == "" or String.first(name) == <<?">> do
+      "nobody"
+    else
+      "Hello, #{name}"
+    end
+  end
+end
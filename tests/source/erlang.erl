@@ -0,0 +1,13 @@
+%% Example module.
+-module(greeter).
+-export([greet/1]).
+
+-doc """
+Greets someone. % not a comment
+""".
+greet(Name) when is_list(Name) ->
+    Percent = $%, % a character, then a comment
+    case Name of
+        [] -> "nobody";
+        _ -> "Hello, " ++ Name ++ [Percent]
+    end.
@@ -0,0 +1,11 @@
+C     Fixed-form example.
+      PROGRAM HELLO
+*     Another comment style
+      INTEGER I
+c     lowercase marker
+      DO 10 I = 1, 3
+         IF (I .GT. 1 .AND. I .LT. 3) PRINT *, 'It''s ', I
+   10 CONTINUE
+!     bang in column 1
+      PRINT *, "Done" ! trailing comment
+      END                                                               !SEQ0001
@@ -0,0 +1,10 @@
+! Free-form example.
+program hello
+  implicit none
+  integer :: i
+  do i = 1, 3
+    if (i > 1 .and. i < 3) print *, 'It''s ', i  ! trailing
+  enddo
+  print *, "Done &
+    &continued"
+end program hello
@@ -0,0 +1,13 @@
+/// Greets someone.
+module Demo
+
+(* outer (* nested *) still a comment *)
+let quote = '"'
+let path = @"C:\temp\""quoted"""
+let raw = """a "quoted" // text"""
+
+let classify n =
+    match n with
+    | 0 -> "zero"
+    | n when n > 0 && n < 10 -> "small" // inline
+    | _ -> "large"
@@ -0,0 +1,15 @@
+package main
+
+import "fmt"
+
+// main prints a raw string.
+func main() {
+	raw := `C:\path\` + "quoted \"text\""
+	multi := `line one
+// not a comment
+line three`
+	/* block */
+	if len(raw) > 0 && len(multi) > 0 {
+		fmt.Println(raw, multi)
+	}
+}
@@ -0,0 +1,18 @@
+{-# LANGUAGE ScopedTypeVariables #-}
+-- | Entry point of the example.
+module Main where
+
+{- A block comment
+   {- nested -} still inside
+-}
+import Data.List (sort)
+
+{-| Sorts and prints. -}
+main :: IO ()
+main = do
+  let xs = sort [3, 1, 2] :: [Int] {- inline -}
+  if null xs && True then putStrLn "{- not a comment -}" else print xs
+  case xs of
+    [] -> return ()
+    _ -> putStrLn "done" -- trailing
+quote = '"' -- a character
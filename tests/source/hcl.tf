@@ -0,0 +1,13 @@
+# Terraform example.
+variable "region" {
+  default = "us-east-1" // trailing
+}
+
+resource "aws_instance" "web" {
+  /* block */
+  user_data = <<-EOT
+    #!/bin/bash
+    echo "hello"
+  EOT
+  count = var.enabled && true ? 1 : 0
+}
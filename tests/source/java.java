@@ -0,0 +1,22 @@
+package demo;
+
+/**
+ * Javadoc for Main.
+ */
+public class Main {
+    /**/
+    int a;
+    int b;
+    // Text blocks span lines.
+    static final String BLOCK = """
+        Hello /* not a comment */
+        World""";
+
+    public static void main(String[] args) {
+        /* block
+           comment */
+        for (String a : args) {
+            System.out.println(a.isEmpty() ? "empty" : a);
+        }
+    }
+}
@@ -0,0 +1,18 @@
+/**
+ * Formats a greeting.
+ */
+export function greet(name = "World") {
+  // template literal spanning lines
+  const text = `Hello,
+${name}!`;
+  return name.length > 0 && text ? text : 'nobody';
+}
+
+const quote = /'/;
+/* istanbul ignore next */
+if (typeof module !== "undefined") {
+  module.exports = { greet };
+}
+let a = 1; /**/
+let b = 2;
+/* end */
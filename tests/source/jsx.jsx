@@ -0,0 +1,14 @@
+import React from "react";
+
+// Renders a list of names.
+export default function Names({ names }) {
+  return (
+    <ul className="names">
+      <p>Don't click</p>
+      {names.length === 0 && <li>None</li>}
+      {names.map((name) => (
+        <li key={name}>{name}</li>
+      ))}
+    </ul>
+  );
+}
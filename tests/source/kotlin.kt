@@ -0,0 +1,16 @@
+package demo
+
+/**
+ * KDoc comment.
+ */
+fun main() {
+    val raw = """
+        C:\path\ // not a comment
+    """.trimIndent()
+    val name: String? = null
+    // when expression
+    when (name ?: "x") {
+        "x" -> println(raw)
+        else -> println("\"$name\"")
+    }
+}
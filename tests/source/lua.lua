@@ -0,0 +1,16 @@
+#!/usr/bin/env lua
+--- Greets someone.
+-- @param name the name
+local function greet(name)
+  --[==[ a long comment
+  with ]] inside ]==]
+  local text = [[
+-- not a comment
+]]
+  if name and #name > 0 then
+    print("Hello, " .. name .. '!') -- trailing
+  end
+  return text
+end
+
+--[[ single line ]] greet("world")
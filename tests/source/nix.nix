@@ -0,0 +1,11 @@
+# Package definition.
+{ pkgs ? import <nixpkgs> {} }:
+
+pkgs.stdenv.mkDerivation {
+  name = "demo";
+  /* block comment */
+  buildPhase = ''
+    echo "# not a comment"
+  '';
+  doCheck = if pkgs.stdenv.isLinux && true then true else false;
+}
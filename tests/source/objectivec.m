@@ -0,0 +1,14 @@
+#import <Foundation/Foundation.h>
+
+/**
+ * A greeter.
+ */
+@implementation Greeter
+// Say hello.
+- (void)greet {
+    NSString *s = @"Hello // World";
+    if (s.length > 0) {
+        NSLog(@"%@", s); /* log it */
+    }
+}
+@end
@@ -0,0 +1,18 @@
+(** Module documentation. *)
+open Printf
+
+(* outer (* nested *) still a comment *)
+let greeting = "(* not a comment *)"
+
+(* Multi-line
+   (* nested
+      comment *)
+   end *)
+let classify n =
+  if n > 0 && n < 10 then "small" (* inline *)
+  else match n with
+    | 0 -> "zero"
+    | _ -> "large"
+
+let () = printf "%s %s\n" greeting (classify 3)
+let quote = '"' (* a character *)
@@ -0,0 +1,17 @@
+#!/usr/bin/perl
+use strict;
+
+=head1 NAME
+
+greet - say hello
+
+=cut
+
+my @items = (1, 2, 3);
+my $last = $#items; # index of the last item
+print <<"EOT";
+Hello # not a comment
+EOT
+print 'done' if $last && @items;
+__END__
+raw data
@@ -0,0 +1,30 @@
+<?php
+/**
+ * Greets visitors.
+ */
+namespace App;
+
+# legacy style comment
+function greet(string $name): string
+{
+    // default to the world
+    if ($name === '' || $name === "?>") {
+        $name = 'World';
+    }
+    return "Hello, $name!";
+}
+?>
+<!-- rendered markup -->
+<p><?= greet($_GET['name'] ?? '') ?></p>
+<?php
+$items = [1, 2, 3];
+foreach ($items as $item) {
+    echo $item;
+}
+$query = <<<SQL
+    SELECT * FROM items -- not a comment
+    WHERE id = 1
+    SQL;
+$raw = <<<'EOT'
+// kept as text
+EOT;
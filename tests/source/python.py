@@ -0,0 +1,27 @@
+#!/usr/bin/env python3
+"""Module docstring.
+
+Spans several lines.
+"""
+import re
+
+
+def greet(name):
+    '''Function docstring.'''
+    pattern = r"\d+ # not a comment"
+    quoted = 'it\'s a "quote" # not a comment'
+    slash = "\\"  # comment after an escaped backslash
+    message = f"Hello, {name}!"  # trailing comment
+    raw = r'''raw # not a comment'''
+    text = f"""
+    {name} # not a comment
+    """
+    if name and re.match(pattern, name):
+        return message
+    elif not name:
+        return 'nobody'
+
+    # A comment with a "quote
+    return """
+    not a # comment
+    """
@@ -0,0 +1,23 @@
+# frozen_string_literal: true
+
+=begin
+Block comment.
+=end
+
+def greet(name)
+  =begin not a comment, but indented =end
+  value =begin_value
+  text = <<~EOS
+    Hello, #{name}!
+    # not a comment
+  EOS
+  query = <<-'SQL'.strip
+    SELECT 1
+  SQL
+  return text if name && !name.empty? # trailing
+  "#{query} nobody"
+end
+
+puts greet("Ruby")
+__END__
+data that is not code
@@ -46,3 +46,12 @@ fn foo() {
     let b = 5;
     let c = 6; // */
 }
+
+fn literals<'a>(s: &'a str) -> &'a str {
+    let (quote, escaped, byte) = ('"', '\'', b'"');
+    let hashes = r####"a "### b"####;
+    let bytes = br##"x "# y"##;
+    let c_str = c"c \" str";
+    'outer: loop { break 'outer; }
+    s
+}
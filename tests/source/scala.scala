@@ -0,0 +1,15 @@
+package demo
+
+/** Scaladoc. */
+object Main {
+  // raw strings keep backslashes
+  val raw = """C:\path\
+    // not a comment"""
+
+  def main(args: Array[String]): Unit = {
+    args.headOption match {
+      case Some(a) if a.nonEmpty => println(a)
+      case _ => println("none") /* fallback */
+    }
+  }
+}
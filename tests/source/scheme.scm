@@ -0,0 +1,10 @@
+; Scheme example.
+(define (square x) (* x x))
+
+#;(define (unused y)
+    (+ y 1))
+(define (classify n)
+  (cond ((> n 0) "small") #;((< n 0) "negative")
+        (else "large #| not a comment |#")))
+
+#| block |# (display (classify #\"))
@@ -0,0 +1,16 @@
+#!/bin/sh
+# Greets someone.
+
+name="${1:-World}"   # default name
+len=${#name}
+mask=$(( (1<<len) - 1 )); (( mask <<= 1 ))
+cat <<EOF
+Hello, $name! # not a comment
+EOF
+cat <<-'END' | tr a-z A-Z
+	literal $name
+	END
+tr a-z A-Z <<< "$name"
+if [ "$len" -gt 0 ] && [ -n "$name" ]; then
+    echo 'single # quoted'
+fi
@@ -0,0 +1,17 @@
+-- Schema for the example.
+CREATE TABLE users (
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL DEFAULT 'it''s -- not a comment'
+);
+
+/* Block comment
+   spanning lines */
+CREATE FUNCTION greet(n TEXT) RETURNS TEXT AS $body$
+BEGIN
+    -- inside the body, still a string
+    RETURN 'Hello, ' || n;
+END;
+$body$ LANGUAGE plpgsql;
+
+SELECT name FROM users WHERE id > 1 AND name <> $$a ' b$$; -- trailing
+SELECT CASE WHEN id = 1 THEN 'one' ELSE 'many' END FROM users;
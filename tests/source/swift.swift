@@ -0,0 +1,14 @@
+import Foundation
+
+/// Greets someone.
+func greet(_ name: String?) -> String {
+    let multi = """
+        Hello, \(name ?? "World")!
+        /* not a comment */
+        """
+    let raw = #"C:\path\"#
+    let hashes = ##"a "# b"##
+    guard let name = name else { return multi }
+    /* outer comment */
+    return raw + name
+}
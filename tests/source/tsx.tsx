@@ -0,0 +1,9 @@
+type Props = { title: string; done?: boolean };
+
+/**
+ * A single task row.
+ */
+export function Task({ title, done }: Props) {
+  const apostrophe = /'/;
+  return <li className={done ? "done" : "open"}>{title}</li>;
+}
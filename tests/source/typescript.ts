@@ -0,0 +1,15 @@
+// Shapes used by the renderer.
+interface Point {
+  x: number;
+  y: number;
+}
+
+/** Distance between two points. */
+export function distance(a: Point, b: Point): number {
+  const dx = a.x - b.x;
+  const dy = a.y - b.y;
+  return Math.sqrt(dx * dx + dy * dy);
+}
+
+const quote = /"/;
+export const origin: Point = { x: 0, y: 0 }; // shared
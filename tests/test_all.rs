@@ -2,4 +2,4 @@
 fn test_all() {
     use nrcc_core::language::LanguageType;
     println!("{:?}", LanguageType::all_language_file());
-}
\ No newline at end of file
+}
@@ -0,0 +1,75 @@
+use nrcc_core::{
+    analysis::DocBlock,
+    language::{LanguageType, SyntaxType},
+    parser::CoreParser,
+};
+
+#[test]
+fn test_doc_blocks() {
+    let mut parser = CoreParser::from_lang(&LanguageType::Rust);
+    parser.init_content(
+        "//! Crate doc.
+//!
+//!     indented
+/// First line.
+/// Second line.
+fn main() {
+    let s = \"/// not a doc\";
+    /// Another block.
+    call();
+}
+",
+    );
+    let blocks = parser.doc_blocks().unwrap();
+    assert_eq!(
+        blocks,
+        [
+            DocBlock {
+                syntax_type: SyntaxType::DocComment,
+                start_line: 1,
+                end_line: 3,
+                text: "Crate doc.\n\n    indented".to_string(),
+                sub_language: None,
+            },
+            DocBlock {
+                syntax_type: SyntaxType::DocComment,
+                start_line: 4,
+                end_line: 5,
+                text: "First line.\nSecond line.".to_string(),
+                sub_language: None,
+            },
+            DocBlock {
+                syntax_type: SyntaxType::DocComment,
+                start_line: 8,
+                end_line: 8,
+                text: "Another block.".to_string(),
+                sub_language: None,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_doc_blocks_captured() {
+    let mut parser = CoreParser::from_lang(&LanguageType::Perl);
+    parser.init_content(
+        "=head1 NAME
+
+Greeter - says hello
+
+=cut
+print \"hello\\n\";
+",
+    );
+    let blocks = parser.doc_blocks().unwrap();
+    assert_eq!(
+        blocks,
+        [DocBlock {
+            syntax_type: SyntaxType::DocMultiComment,
+            start_line: 1,
+            end_line: 5,
+            text: "NAME\n\nGreeter - says hello".to_string(),
+            sub_language: None,
+        }]
+    );
+}
@@ -0,0 +1,116 @@
+use nrcc_core::{
+    language::LanguageType,
+    parser::{CommentResult, CoreParser, ParseResult},
+};
+
+const RUST: &str = r#"/// Adds one.
+///
+/// ```
+/// // the answer
+/// let x = add_one(41);
+///
+/// assert_eq!(x, 42);
+/// ```
+///
+/// ```text
+/// plain output
+/// ```
+fn add_one(x: i32) -> i32 {
+    x + 1
+}
+"#;
+
+#[test]
+fn test_doc_code_rust() {
+    let mut parser = CoreParser::from_lang(&LanguageType::Rust);
+    parser.init_content(RUST);
+    parser.init_doc_code(true);
+    let rust = ParseResult {
+        code: 2,
+        blank: 1,
+        all: 4,
+        comment: CommentResult {
+            normal: 1,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let text = ParseResult {
+        code: 1,
+        all: 1,
+        ..Default::default()
+    };
+    assert_eq!(
+        parser.parse().unwrap(),
+        ParseResult {
+            code: 6,
+            blank: 1,
+            all: 15,
+            comment: CommentResult {
+                normal: 1,
+                doc: 7,
+                ..Default::default()
+            },
+            sub_language: [(LanguageType::Rust, rust), (LanguageType::Text, text)].into(),
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn test_doc_code_python() {
+    let mut parser = CoreParser::from_lang(&LanguageType::Python);
+    parser.init_content(
+        r#"def add(a, b):
+    """Add two numbers.
+
+    >>> add(1, 2)
+    3
+    >>> for i in range(2):
+    ...     print(add(i, i))
+    0
+    2
+    """
+    return a + b
+"#,
+    );
+    parser.init_doc_code(true);
+    let python = ParseResult {
+        code: 3,
+        all: 3,
+        complexity: 1,
+        ..Default::default()
+    };
+    assert_eq!(
+        parser.parse().unwrap(),
+        ParseResult {
+            code: 11,
+            all: 11,
+            complexity: 1,
+            comment: CommentResult {
+                doc_quote: 6,
+                ..Default::default()
+            },
+            sub_language: [(LanguageType::Python, python)].into(),
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn test_doc_code_off() {
+    let mut parser = CoreParser::from_lang(&LanguageType::Rust);
+    parser.init_content(RUST);
+    assert_eq!(
+        parser.parse().unwrap(),
+        ParseResult {
+            code: 3,
+            all: 15,
+            comment: CommentResult {
+                doc: 12,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    );
+}
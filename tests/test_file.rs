@@ -0,0 +1,42 @@
+use std::path::Path;
+
+use nrcc_core::language::LanguageType;
+
+#[test]
+fn test_file_path_glob() {
+    let detect = |path: &str| LanguageType::from_path(Path::new(path));
+    assert_eq!(
+        detect(".github/workflows/ci.yml"),
+        Some(LanguageType::GitHubActions)
+    );
+    assert_eq!(
+        detect("/home/me/repo/.github/workflows/release.yaml"),
+        Some(LanguageType::GitHubActions)
+    );
+    assert_eq!(
+        detect(".github/workflows/nested/ci.yml"),
+        Some(LanguageType::Yaml)
+    );
+    assert_eq!(detect("ci.yml"), Some(LanguageType::Yaml));
+    assert_eq!(detect("ci/Jenkinsfile"), Some(LanguageType::Groovy));
+    assert_eq!(detect("MyJenkinsfile"), None);
+    assert_eq!(
+        detect("arch/arm64/kernel/head.S"),
+        Some(LanguageType::ArmAssembly)
+    );
+    assert_eq!(
+        detect("arch/x86/kernel/head_64.S"),
+        Some(LanguageType::GasAssembly)
+    );
+}
+
+#[test]
+fn test_file_regex() {
+    let detect = |path: &str| LanguageType::from_path(Path::new(path));
+    assert_eq!(
+        detect("docker/Dockerfile.dev"),
+        Some(LanguageType::Dockerfile)
+    );
+    assert_eq!(detect("docker/NotDockerfile.dev"), None);
+    assert_eq!(detect("docker/Dockerfile.dev~"), None);
+}
@@ -0,0 +1,112 @@
+use nrcc_core::{
+    language::LanguageType,
+    parser::{CommentResult, CoreParser, ParseResult},
+};
+
+fn code(code: usize) -> ParseResult {
+    ParseResult {
+        code,
+        all: code,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_injection_rust() {
+    let mut parser = CoreParser::from_lang(&LanguageType::Rust);
+    parser.init_content(
+        r##"let re = Regex::new(r"^\d+$").unwrap();
+// language=GraphQL
+let query = "{ user { id } }";
+let users = sqlx::query!(r#"
+    SELECT id -- the key
+    FROM users
+"#);
+let plain = "not injected";
+"##,
+    );
+    let sql = ParseResult {
+        comment: CommentResult {
+            normal: 1,
+            ..Default::default()
+        },
+        ..code(2)
+    };
+    assert_eq!(
+        parser.parse().unwrap(),
+        ParseResult {
+            code: 7,
+            all: 8,
+            comment: CommentResult {
+                normal: 2,
+                ..Default::default()
+            },
+            sub_language: [
+                (LanguageType::GraphQL, code(1)),
+                (LanguageType::Regex, code(1)),
+                (LanguageType::Sql, sql),
+            ]
+            .into(),
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn test_injection_python() {
+    let mut parser = CoreParser::from_lang(&LanguageType::Python);
+    parser.init_content(
+        r#"import re
+PATTERN = re.compile(r"[a-z]+\d*")
+cursor.execute("""
+    SELECT name
+    FROM users
+""")
+# language=json
+CONFIG = '{"debug": true}'
+"#,
+    );
+    let result = parser.parse().unwrap();
+    assert_eq!((result.code, result.all, result.comment.normal), (7, 8, 1));
+    assert_eq!(result.comment.doc_quote, 0);
+    assert_eq!(
+        result.sub_language,
+        [
+            (LanguageType::Json, code(1)),
+            (LanguageType::Regex, code(1)),
+            (LanguageType::Sql, code(2)),
+        ]
+        .into()
+    );
+}
+
+#[test]
+fn test_injection_tagged_template() {
+    let mut parser = CoreParser::from_lang(&LanguageType::JavaScript);
+    parser.init_content(
+        "const query = gql`
+  query { viewer { login } }
+`;
+const text = `plain`;
+",
+    );
+    let result = parser.parse().unwrap();
+    assert_eq!((result.code, result.all), (4, 4));
+    assert_eq!(
+        result.sub_language,
+        [(LanguageType::GraphQL, code(1))].into()
+    );
+}
+
+#[test]
+fn test_injection_unknown_language() {
+    let mut parser = CoreParser::from_lang(&LanguageType::Rust);
+    parser.init_content(
+        r#"// the language = en
+let greeting = "hello";
+"#,
+    );
+    let result = parser.parse().unwrap();
+    assert_eq!((result.code, result.all, result.comment.normal), (1, 2, 1));
+    assert!(result.sub_language.is_empty());
+}
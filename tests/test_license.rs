@@ -0,0 +1,40 @@
+use std::path::Path;
+
+use nrcc_core::{analysis::LicenseInfo, language::LanguageType, parser::CoreParser};
+
+#[test]
+fn test_license() {
+    let mut parser = CoreParser::from_lang(&LanguageType::Rust);
+    parser.init_content(
+        "// Copyright 2023 Someone
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Licensed under the Apache License,
+// Version 2.0 (the \"License\");
+
+/* SPDX-License-Identifier: MIT */
+fn main() {}
+// SPDX-License-Identifier: GPL-3.0
+",
+    );
+    let report = parser
+        .report()
+        .unwrap()
+        .with_license(parser.license().unwrap());
+    assert_eq!(
+        report.license,
+        Some(LicenseInfo {
+            header: Some((1, 7)),
+            spdx: vec!["MIT OR Apache-2.0".to_string(), "MIT".to_string()],
+            detected: vec!["Apache-2.0"],
+        })
+    );
+}
+
+#[test]
+fn test_license_missing() {
+    let parser = CoreParser::from_path(Path::new("./tests/source/rust.rs")).unwrap();
+    let license = parser.license().unwrap();
+    assert_eq!(license.header, Some((1, 9)));
+    assert!(license.is_missing());
+}
@@ -0,0 +1,50 @@
+use std::path::Path;
+
+use nrcc_core::{
+    analysis::{Marker, DEFAULT_MARKERS},
+    language::LanguageType,
+    parser::CoreParser,
+};
+
+#[test]
+fn test_markers() {
+    let mut parser = CoreParser::from_lang(&LanguageType::Rust);
+    parser.init_content(
+        "let s = \"TODO: not a comment\"; // TODO: fix this
+let TODO_LIST = 1; /* FIXME(someone) spans
+   lines, HACK: here */
+// NOTE custom marker",
+    );
+    let markers = parser
+        .find_markers(&[DEFAULT_MARKERS, &["NOTE"]].concat())
+        .unwrap();
+    let found: Vec<_> = markers
+        .iter()
+        .map(|m| (m.line, m.marker.as_str(), m.text.as_str()))
+        .collect();
+    assert_eq!(
+        found,
+        [
+            (1, "TODO", "fix this"),
+            (2, "FIXME", "(someone) spans"),
+            (3, "HACK", "here"),
+            (4, "NOTE", "custom marker"),
+        ]
+    );
+}
+
+#[test]
+fn test_markers_file() {
+    let path = Path::new("./tests/source/rust.rs");
+    let parser = CoreParser::from_path(path).unwrap();
+    let markers = parser.find_markers(&["comment"]).unwrap();
+    assert_eq!(
+        markers[0],
+        Marker {
+            file: Some(path.to_path_buf()),
+            line: 14,
+            marker: "comment".to_string(),
+            text: String::new(),
+        }
+    );
+}
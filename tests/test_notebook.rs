@@ -0,0 +1,37 @@
+use nrcc_core::{
+    error::CoreError,
+    language::LanguageType,
+    parser::{CoreParser, ParseResult},
+};
+
+fn notebook(metadata: &str) -> CoreParser {
+    let mut parser = CoreParser::from_lang(&LanguageType::Jupyter);
+    parser.init_content(&format!(
+        r#"{{"metadata": {metadata}, "nbformat": 4, "cells": [
+            {{"cell_type": "code", "source": ["x = 1\n", "print(x)"]}}
+        ]}}"#
+    ));
+    parser
+}
+
+fn languages(parser: &CoreParser) -> Vec<LanguageType> {
+    let result: ParseResult = parser.parse().unwrap();
+    result.sub_language.into_keys().collect()
+}
+
+#[test]
+fn test_notebook_language() {
+    let unknown = r#"{"kernelspec": {"language": "brainfuck"}}"#;
+    assert_eq!(languages(&notebook(unknown)), [LanguageType::Text]);
+    let info = r#"{"kernelspec": {"language": "brainfuck"}, "language_info": {"name": "R"}}"#;
+    assert_eq!(languages(&notebook(info)), [LanguageType::R]);
+    assert_eq!(languages(&notebook("{}")), [LanguageType::Python]);
+}
+
+#[test]
+fn test_notebook_segments() {
+    assert!(matches!(
+        notebook("{}").segments(),
+        Err(CoreError::Unsupported(_))
+    ));
+}
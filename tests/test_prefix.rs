@@ -0,0 +1,49 @@
+use nrcc_core::{
+    language::{LanguageType, MissingPrefix},
+    parser::{CommentResult, CoreParser, ParseResult},
+};
+
+const CONTENT: &str = "> x = 1
+  | y = 2
+Some prose
+
+> # comment
+";
+
+fn parse(missing_prefix: MissingPrefix, ignore_prefix_space: bool) -> ParseResult {
+    let mut syntax = LanguageType::Python.get_language_syntax();
+    syntax.line_prefix = &["> ", "| "];
+    syntax.missing_prefix = missing_prefix;
+    syntax.ignore_prefix_space = ignore_prefix_space;
+    CoreParser::parse_lines(CONTENT.lines(), &syntax).unwrap()
+}
+
+fn counts(code: usize, blank: usize, doc: usize) -> ParseResult {
+    ParseResult {
+        code,
+        blank,
+        all: 5,
+        comment: CommentResult {
+            doc,
+            normal: 1,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_missing_prefix() {
+    assert_eq!(parse(MissingPrefix::Prose, true), counts(2, 1, 1));
+    assert_eq!(parse(MissingPrefix::Blank, true), counts(2, 2, 0));
+    assert_eq!(parse(MissingPrefix::Code, true), counts(3, 1, 0));
+    assert_eq!(parse(MissingPrefix::Prose, false), counts(1, 1, 2));
+}
+
+#[test]
+fn test_missing_prefix_error() {
+    let mut syntax = LanguageType::Python.get_language_syntax();
+    syntax.line_prefix = &["> "];
+    syntax.missing_prefix = MissingPrefix::Error;
+    assert!(CoreParser::parse_lines(CONTENT.lines(), &syntax).is_err());
+}
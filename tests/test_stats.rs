@@ -0,0 +1,24 @@
+use nrcc_core::{analysis::TextStats, language::LanguageType, parser::CoreParser};
+
+#[test]
+fn test_stats() {
+    let mut parser = CoreParser::from_lang(&LanguageType::Rust);
+    parser.init_content("let s = \"漢字\"; // note\n\tcall();\r\n\n");
+    let (result, stats) = parser.parse_with_stats(10).unwrap();
+    assert_eq!(result, parser.parse().unwrap());
+    assert_eq!(
+        stats,
+        TextStats {
+            bytes: 37,
+            code_bytes: 18,
+            comment_bytes: 7,
+            string_bytes: 8,
+            max_line_chars: 21,
+            max_line_columns: 23,
+            long_lines: 2,
+        }
+    );
+    let total = stats + stats;
+    assert_eq!(total.bytes, 74);
+    assert_eq!(total.max_line_columns, 23);
+}
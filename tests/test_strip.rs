@@ -0,0 +1,92 @@
+use nrcc_core::{analysis::StripOptions, language::LanguageType, parser::CoreParser};
+
+const SOURCE: &str = "//! Module doc
+/// Item doc
+fn main() {
+    // comment
+    let s = \"// not a comment\"; /* trailing */
+    call(/* inline */ 1);
+    /* multi
+       line */
+}
+";
+
+#[test]
+fn test_strip() {
+    let mut parser = CoreParser::from_lang(&LanguageType::Rust);
+    parser.init_content(SOURCE);
+    assert_eq!(
+        parser.strip_comments(&StripOptions::default()).unwrap(),
+        "//! Module doc
+/// Item doc
+fn main() {
+
+    let s = \"// not a comment\";
+    call( 1);
+
+
+}
+"
+    );
+    assert_eq!(
+        parser
+            .strip_comments(&StripOptions {
+                doc: true,
+                collapse_blank: true,
+            })
+            .unwrap(),
+        "fn main() {
+    let s = \"// not a comment\";
+    call( 1);
+}
+"
+    );
+}
+
+#[test]
+fn test_strip_columns() {
+    // The sequence area after column 72 stays in its columns.
+    let content = format!(
+        "000100{:<66}SEQ00001\n000200{:<66}SEQ00002\n000300{:<66}SEQ00003\n",
+        "       MOVE 1 TO X. *> set x", "* whole line", "       STOP RUN.",
+    );
+    let mut parser = CoreParser::from_lang(&LanguageType::Cobol);
+    parser.init_content(&content);
+    assert_eq!(
+        parser.strip_comments(&StripOptions::default()).unwrap(),
+        format!(
+            "000100{:<66}SEQ00001\n\n{}\n",
+            "       MOVE 1 TO X.",
+            content.lines().nth(2).unwrap(),
+        )
+    );
+}
+
+#[test]
+fn test_strip_doc_code() {
+    let source = "/// ```
+/// let x = 1; // c
+/// ```
+fn f() {} // trailing
+";
+    let mut parser = CoreParser::from_lang(&LanguageType::Rust);
+    parser.init_content(source);
+    parser.init_doc_code(true);
+    assert_eq!(
+        parser.strip_comments(&StripOptions::default()).unwrap(),
+        "/// ```
+/// let x = 1; // c
+/// ```
+fn f() {}
+"
+    );
+    assert_eq!(
+        parser
+            .strip_comments(&StripOptions {
+                doc: true,
+                collapse_blank: true,
+            })
+            .unwrap(),
+        "fn f() {}\n"
+    );
+}
@@ -0,0 +1,56 @@
+use std::path::Path;
+
+use nrcc_core::{
+    language::LanguageType,
+    parser::{CommentResult, CoreParser, ParseResult},
+};
+
+#[test]
+fn test_template_file() {
+    let parser = CoreParser::from_path(Path::new("./tests/source/page.html.j2")).unwrap();
+    assert_eq!(parser.language(), LanguageType::Html);
+    assert_eq!(parser.syntax().template, Some(LanguageType::Jinja));
+    let jinja = ParseResult {
+        code: 4,
+        all: 5,
+        complexity: 1,
+        comment: CommentResult {
+            normal: 1,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    assert_eq!(
+        parser.parse().unwrap(),
+        ParseResult {
+            code: 7,
+            all: 8,
+            complexity: 1,
+            comment: jinja.comment,
+            sub_language: [(LanguageType::Jinja, jinja)].into(),
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn test_template_yaml() {
+    let mut parser = CoreParser::from_template(&LanguageType::Yaml, &LanguageType::GoTemplate);
+    parser.init_content(
+        "# Chart values.
+replicas: {{ .Values.replicas }}
+{{- /* Optional image tag. */ -}}
+{{ if .Values.tag }}
+tag: {{ .Values.tag | quote }}
+{{ end }}
+",
+    );
+    let result = parser.parse().unwrap();
+    assert_eq!((result.code, result.all, result.comment.normal), (4, 6, 2));
+    let template = &result.sub_language[&LanguageType::GoTemplate];
+    assert_eq!(
+        (template.code, template.all, template.comment.normal),
+        (4, 5, 1)
+    );
+    assert_eq!(template.complexity, 1);
+}